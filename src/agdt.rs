@@ -0,0 +1,387 @@
+use crate::fields::{TagReader, Unrepresentable};
+use crate::parse::*;
+use crate::string::{ParseError, ParseErrorKind};
+
+// The Ancient Greek Dependency Treebank and the Perseus data tag each
+// word with a nine character postag, such as "v3saia---", that holds
+// the part of speech, person, number, tense, mood, voice, gender, case
+// and degree in that order. A '-' marks an empty field.
+//
+// Some treebank values have no place in a parsing, such as the
+// locative case, a degree on a pronoun, the person of a third person
+// pronoun, the gender of a first or second person pronoun or the 'u'
+// punctuation tag.
+// from_tag() reports these rather than failing. to_tag() loses detail
+// the postag can not hold: second tenses are written as the first
+// tense, deponents as the plain voice, and every class of pronoun as
+// 'p'.
+
+const TAG_LENGTH: usize = 9;
+
+const NAMES: &[&str; TAG_LENGTH] = &[
+    "part of speech",
+    "person",
+    "number",
+    "tense",
+    "mood",
+    "voice",
+    "gender",
+    "case",
+    "degree",
+];
+
+// from_tag reads a postag into a parsing, with the values in the tag
+// that could not be stored.
+pub fn from_tag(tag: &str) -> Result<(u32, Vec<Unrepresentable>), ParseError> {
+    let mut reader = TagReader::new(tag.to_string(), NAMES);
+
+    let chars: Vec<char> = tag.chars().collect();
+    if chars.is_empty() {
+        return Err(reader.fail(ParseErrorKind::Incomplete(UNKNOWN), 0));
+    }
+
+    let mut parsing = match chars[0] {
+        'v' | 't' => VERB,
+        'n' => NOUN,
+        'a' => ADJECTIVE,
+        'd' => ADVERB,
+        'l' => ARTICLE,
+        'g' => PARTICLE,
+        'c' => CONJUNCTION,
+        'r' => PREPOSITION,
+        'p' => PRONOUN,
+        'm' => NUMERAL,
+        'i' | 'e' => INTERJECTION,
+        // Punctuation has no part of speech here, and is reported
+        // below rather than failing a whole treebank on its first comma.
+        'x' | '-' | 'u' => UNKNOWN,
+        c => return Err(reader.fail(ParseErrorKind::UnknownPartOfSpeech(c.to_string()), 0)),
+    };
+    if chars.len() < TAG_LENGTH {
+        return Err(reader.fail(ParseErrorKind::Incomplete(parsing), chars.len()));
+    }
+    if chars.len() > TAG_LENGTH {
+        return Err(reader.fail(
+            ParseErrorKind::UnexpectedCharacter(parsing, chars[TAG_LENGTH]),
+            TAG_LENGTH,
+        ));
+    }
+
+    if chars[0] == 'u' {
+        reader.lose(0, 0, 'u');
+    }
+    let verb = parsing == VERB;
+    let pronoun = parsing == PRONOUN;
+    for (index, &c) in chars.iter().enumerate().skip(1) {
+        if c == '-' {
+            continue;
+        }
+        let value = match index {
+            1 => match c {
+                '1' => FIRST_PERSON,
+                '2' => SECOND_PERSON,
+                '3' => THIRD_PERSON,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownPerson(parsing, c), index)),
+            },
+            2 => match c {
+                's' => SINGULAR,
+                'd' => DUAL,
+                'p' => PLURAL,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownNumber(parsing, c), index)),
+            },
+            3 => match c {
+                'p' => PRESENT,
+                'i' => IMPERFECT,
+                'r' => PERFECT,
+                'l' => PLUPERFECT,
+                't' => FUTURE_PERFECT,
+                'f' => FUTURE,
+                'a' => AORIST,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownTenseForm(parsing, c), index)),
+            },
+            4 => match c {
+                'i' => INDICATIVE_MOOD,
+                's' => SUBJUNCTIVE_MOOD,
+                'o' => OPTATIVE_MOOD,
+                'n' => INFINITIVE_MOOD,
+                'm' => IMPERATIVE_MOOD,
+                'p' => PARTICIPLE_MOOD,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownMood(parsing, c), index)),
+            },
+            5 => match c {
+                'a' => ACTIVE_VOICE,
+                'p' => PASSIVE_VOICE,
+                'm' => MIDDLE_VOICE,
+                'e' => MIDDLE_PASSIVE_VOICE,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownVoice(parsing, c), index)),
+            },
+            6 => match c {
+                'm' => MASCULINE,
+                'f' => FEMININE,
+                'n' => NEUTER,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownGender(parsing, c), index)),
+            },
+            7 => match c {
+                'n' => NOMINATIVE,
+                'g' => GENITIVE,
+                'd' => DATIVE,
+                'a' => ACCUSATIVE,
+                'v' => VOCATIVE,
+                'l' => UNKNOWN,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownCase(parsing, c), index)),
+            },
+            _ => match (part_of_speech(parsing), c) {
+                (NOUN, 'c') => COMPARATIVE_NOUN,
+                (NOUN, 's') => SUPERLATIVE_NOUN,
+                (ADJECTIVE, 'c') => COMPARATIVE_ADJECTIVE,
+                (ADJECTIVE, 's') => SUPERLATIVE_ADJECTIVE,
+                (ADVERB, 'c') => COMPARATIVE_ADVERB,
+                (ADVERB, 's') => SUPERLATIVE_ADVERB,
+                (_, 'p' | 'c' | 's') => UNKNOWN,
+                _ => {
+                    return Err(reader.fail(ParseErrorKind::UnexpectedCharacter(parsing, c), index))
+                }
+            },
+        };
+
+        // Only verbs carry a tense, mood and voice. A pronoun with a
+        // person is a personal pronoun, but the Robinson code only has
+        // room for a first or second person, as in P-1NS, and those are
+        // written without a gender. A positive degree is the same as
+        // none.
+        if index == 1 && pronoun {
+            parsing = set_part_of_speech(parsing, PERSONAL_PRONOUN);
+        }
+        let fits = match index {
+            1 => verb || (pronoun && value != THIRD_PERSON),
+            3..=5 => verb,
+            6 => part_of_speech(parsing) != PERSONAL_PRONOUN || person(parsing) == 0,
+            8 => value != UNKNOWN || c == 'p',
+            _ => value != UNKNOWN,
+        };
+        if !fits {
+            reader.lose(index, index, c);
+        } else if index == 8 {
+            if value != UNKNOWN {
+                parsing = set_part_of_speech(parsing, value);
+            }
+        } else {
+            parsing |= value;
+        }
+    }
+
+    Ok(reader.finish(parsing))
+}
+
+// to_tag writes a parsing as a postag.
+pub fn to_tag(parsed: u32) -> String {
+    let pos = part_of_speech(parsed);
+    let verb = pos == VERB;
+    let (p, degree) = match pos {
+        VERB => ('v', '-'),
+        NOUN
+        | PROPER_NOUN
+        | LETTER
+        | TRANSLITERATION
+        | HEBREW_TRANSLITERATION
+        | ARAMAIC_TRANSLITERATION => ('n', '-'),
+        COMPARATIVE_NOUN => ('n', 'c'),
+        SUPERLATIVE_NOUN => ('n', 's'),
+        ADJECTIVE => ('a', '-'),
+        COMPARATIVE_ADJECTIVE => ('a', 'c'),
+        SUPERLATIVE_ADJECTIVE => ('a', 's'),
+        ADVERB => ('d', '-'),
+        COMPARATIVE_ADVERB => ('d', 'c'),
+        SUPERLATIVE_ADVERB => ('d', 's'),
+        ARTICLE => ('l', '-'),
+        PARTICLE => ('g', '-'),
+        CONJUNCTION | CONDITIONAL => ('c', '-'),
+        PREPOSITION => ('r', '-'),
+        PRONOUN
+        | PERSONAL_PRONOUN
+        | POSSESSIVE_PRONOUN
+        | RELATIVE_PRONOUN
+        | DEMONSTRATIVE_PRONOUN
+        | RECIPROCAL_PRONOUN
+        | REFLEXIVE_PRONOUN => ('p', '-'),
+        NUMERAL => ('m', '-'),
+        INTERJECTION => ('i', '-'),
+        _ => ('-', '-'),
+    };
+
+    let mut s = String::with_capacity(TAG_LENGTH);
+    s.push(p);
+    s.push(match person(parsed) {
+        FIRST_PERSON if p == 'v' || p == 'p' => '1',
+        SECOND_PERSON if p == 'v' || p == 'p' => '2',
+        THIRD_PERSON if p == 'v' || p == 'p' => '3',
+        _ => '-',
+    });
+    s.push(match number(parsed) {
+        SINGULAR => 's',
+        DUAL => 'd',
+        PLURAL => 'p',
+        _ => '-',
+    });
+    if verb {
+        s.push(match tense(parsed) {
+            PRESENT => 'p',
+            IMPERFECT => 'i',
+            PERFECT | SECOND_PERFECT => 'r',
+            PLUPERFECT | SECOND_PLUPERFECT => 'l',
+            FUTURE_PERFECT | SECOND_FUTURE_PERFECT => 't',
+            FUTURE | SECOND_FUTURE => 'f',
+            AORIST | SECOND_AORIST => 'a',
+            _ => '-',
+        });
+        s.push(match mood(parsed) {
+            INDICATIVE_MOOD => 'i',
+            SUBJUNCTIVE_MOOD => 's',
+            OPTATIVE_MOOD => 'o',
+            INFINITIVE_MOOD => 'n',
+            IMPERATIVE_MOOD => 'm',
+            PARTICIPLE_MOOD => 'p',
+            _ => '-',
+        });
+        s.push(match voice(parsed) {
+            ACTIVE_VOICE => 'a',
+            PASSIVE_VOICE | PASSIVE_DEPONENT_VOICE => 'p',
+            MIDDLE_VOICE | MIDDLE_DEPONENT_VOICE => 'm',
+            MIDDLE_PASSIVE_VOICE | MIDDLE_PASSIVE_DEPONENT_VOICE => 'e',
+            _ => '-',
+        });
+    } else {
+        s.push_str("---");
+    }
+    s.push(match gender(parsed) {
+        MASCULINE => 'm',
+        FEMININE => 'f',
+        NEUTER => 'n',
+        _ => '-',
+    });
+    s.push(match case(parsed) {
+        NOMINATIVE => 'n',
+        GENITIVE => 'g',
+        DATIVE => 'd',
+        ACCUSATIVE => 'a',
+        VOCATIVE => 'v',
+        _ => '-',
+    });
+    s.push(degree);
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_agdt_tags() {
+        for (tag, code) in [
+            ("v3saia---", "V-AAI-3S"),
+            ("n-s---mn-", "N-NSM"),
+            ("v-pppemn-", "V-PEP-NPM"),
+            ("v--ana---", "V-AAN"),
+            ("v2dpma---", "V-PAM-2D"),
+            ("a-p---fdc", "A-DPF-C"),
+            ("l-s---ng-", "T-GSN"),
+            ("d--------", "ADV"),
+            ("d-------s", "ADV-S"),
+            ("c--------", "CONJ"),
+        ] {
+            let (parsed, lost) = match from_tag(tag) {
+                Ok(result) => result,
+                Err(e) => panic!("{}: {}", tag, e),
+            };
+            assert_eq!(to_string(parsed), code, "{}", tag);
+            assert!(lost.is_empty(), "{}: {:?}", tag, lost);
+            assert_eq!(to_tag(parsed), tag);
+        }
+        assert_eq!(to_tag(from_string("V-2AMP-NPM").unwrap()), "v-papmmn-");
+        assert_eq!(to_tag(from_string("S-1SDSN").unwrap()), "p1s---nd-");
+    }
+
+    #[test]
+    fn test_agdt_unrepresentable() {
+        let (parsed, lost) = from_tag("n-s---ml-").unwrap();
+        assert_eq!(parsed, NOUN | SINGULAR | MASCULINE);
+        assert_eq!(
+            lost,
+            vec![Unrepresentable {
+                position: 7,
+                value: 'l',
+                name: "case"
+            }]
+        );
+        assert_eq!(
+            lost[0].to_string(),
+            "case 'l' at position 8 can not be represented"
+        );
+
+        let (parsed, lost) = from_tag("p-s---mnc").unwrap();
+        assert_eq!(parsed, PRONOUN | SINGULAR | MASCULINE | NOMINATIVE);
+        assert_eq!(lost[0].name, "degree");
+
+        // Pronouns with a person are read as personal pronouns, and
+        // whatever the Robinson code can not write is reported.
+        let (parsed, lost) = from_tag("p1s---mn-").unwrap();
+        assert_eq!(
+            parsed,
+            PERSONAL_PRONOUN | FIRST_PERSON | SINGULAR | NOMINATIVE
+        );
+        assert_eq!(
+            lost,
+            vec![Unrepresentable {
+                position: 6,
+                value: 'm',
+                name: "gender"
+            }]
+        );
+        assert_eq!(to_string(parsed), "P-1NS");
+        let (parsed, lost) = from_tag("p3s---mn-").unwrap();
+        assert_eq!(parsed, PERSONAL_PRONOUN | SINGULAR | MASCULINE | NOMINATIVE);
+        assert_eq!(
+            lost,
+            vec![Unrepresentable {
+                position: 1,
+                value: '3',
+                name: "person"
+            }]
+        );
+        assert_eq!(to_string(parsed), "P-NSM");
+        for tag in ["p1s---mn-", "p2p----d-", "p3s---mn-", "p3p---fa-"] {
+            let (parsed, _) = from_tag(tag).unwrap();
+            assert_eq!(crate::validate::validate(parsed), Ok(()), "{}", tag);
+            assert_eq!(from_string(&to_string(parsed)), Ok(parsed), "{}", tag);
+        }
+        assert_eq!(from_tag("p2p----d-").unwrap().1, vec![]);
+        assert_eq!(from_tag("a-s---mnp").unwrap().1, vec![]);
+
+        let e = from_tag("v3sqia---").unwrap_err();
+        assert_eq!(
+            e.kind,
+            ParseErrorKind::UnknownTenseForm(VERB | THIRD_PERSON | SINGULAR, 'q')
+        );
+        assert_eq!(e.position, 3);
+        assert_eq!(
+            from_tag("u--------"),
+            Ok((
+                UNKNOWN,
+                vec![Unrepresentable {
+                    position: 0,
+                    value: 'u',
+                    name: "part of speech"
+                }]
+            ))
+        );
+        assert_eq!(
+            from_tag("z--------").unwrap_err().kind,
+            ParseErrorKind::UnknownPartOfSpeech("z".to_string())
+        );
+        assert_eq!(
+            from_tag("n-s").unwrap_err().kind,
+            ParseErrorKind::Incomplete(NOUN)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// Biblical Aramaic codes from the Open Scriptures Hebrew Bible start
+// with A rather than H, as in AVqp3ms. They are read and written by the
+// functions in hebrew.rs and stored in the same layout with the ARAMAIC
+// bit set. Only the verb stems and the states differ from Hebrew. The
+// stems below reuse the Hebrew stem bits and the states the Hebrew
+// state bits, so the language bit must be checked before comparing
+// either against a constant.
+
+// Verb stem bits 9-14
+pub const PEAL: u32 = 1 << 9;
+pub const PEIL: u32 = 2 << 9;
+pub const HITHPEEL: u32 = 3 << 9;
+pub const PAEL: u32 = 4 << 9;
+pub const ITHPAAL: u32 = 5 << 9;
+pub const HITHPAAL: u32 = 6 << 9;
+pub const APHEL: u32 = 7 << 9;
+pub const HAPHEL: u32 = 8 << 9;
+pub const SAPHEL: u32 = 9 << 9;
+pub const SHAPHEL: u32 = 10 << 9;
+pub const HOPHAL: u32 = 11 << 9;
+pub const ITHPEEL: u32 = 12 << 9;
+pub const HISHTAPHEL: u32 = 13 << 9;
+pub const ISHTAPHEL: u32 = 14 << 9;
+pub const HITHAPHEL: u32 = 15 << 9;
+pub const POLEL: u32 = 16 << 9;
+pub const ITHPOEL: u32 = 17 << 9;
+pub const HITHPOLEL: u32 = 18 << 9;
+pub const HITHPALPEL: u32 = 19 << 9;
+pub const HEPHAL: u32 = 20 << 9;
+pub const TIPHEL: u32 = 21 << 9;
+pub const POEL: u32 = 22 << 9;
+pub const PALPEL: u32 = 23 << 9;
+pub const ITHPALPEL: u32 = 24 << 9;
+pub const ITHPOLEL: u32 = 25 << 9;
+pub const ITTAPHAL: u32 = 26 << 9;
+
+// State bits 26-27. Absolute and construct are the Hebrew values.
+pub const ABSOLUTE: u32 = crate::hebrew::ABSOLUTE;
+pub const CONSTRUCT: u32 = crate::hebrew::CONSTRUCT;
+// Also called the emphatic state.
+pub const DETERMINED: u32 = 3 << 26;
+
+pub(crate) const STEMS: &[(char, u32)] = &[
+    ('q', PEAL),
+    ('Q', PEIL),
+    ('u', HITHPEEL),
+    ('p', PAEL),
+    ('P', ITHPAAL),
+    ('M', HITHPAAL),
+    ('a', APHEL),
+    ('h', HAPHEL),
+    ('s', SAPHEL),
+    ('e', SHAPHEL),
+    ('H', HOPHAL),
+    ('i', ITHPEEL),
+    ('t', HISHTAPHEL),
+    ('v', ISHTAPHEL),
+    ('w', HITHAPHEL),
+    ('o', POLEL),
+    ('z', ITHPOEL),
+    ('r', HITHPOLEL),
+    ('f', HITHPALPEL),
+    ('b', HEPHAL),
+    ('c', TIPHEL),
+    ('m', POEL),
+    ('l', PALPEL),
+    ('L', ITHPALPEL),
+    ('O', ITHPOLEL),
+    ('G', ITTAPHAL),
+];
+pub(crate) const STATES: &[(char, u32)] = &[('a', ABSOLUTE), ('c', CONSTRUCT), ('d', DETERMINED)];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hebrew::{
+        from_string, is_aramaic, parse_word, to_string, word_to_string, HebrewError, ARAMAIC,
+        COMMON_NOUN, DEFINITE_ARTICLE, MASCULINE, NOUN, PARTICLE, PERFECT, PLURAL, SINGULAR,
+        THIRD_PERSON, VERB,
+    };
+
+    #[test]
+    fn test_aramaic() {
+        assert_eq!(
+            from_string("AVqp3ms"),
+            Ok(ARAMAIC | VERB | PEAL | PERFECT | THIRD_PERSON | MASCULINE | SINGULAR)
+        );
+        assert_eq!(
+            from_string("ANcmpd"),
+            Ok(ARAMAIC | NOUN | COMMON_NOUN | MASCULINE | PLURAL | DETERMINED)
+        );
+        assert_eq!(
+            parse_word("ATd/Ncmsa"),
+            Ok(vec![
+                ARAMAIC | PARTICLE | DEFINITE_ARTICLE,
+                ARAMAIC | NOUN | COMMON_NOUN | MASCULINE | SINGULAR | ABSOLUTE
+            ])
+        );
+        assert!(is_aramaic(from_string("AVap3ms").unwrap()));
+
+        // The same letters mean different stems and states in Hebrew.
+        assert_eq!(
+            from_string("HNcmpd"),
+            Err(HebrewError::Unknown("state", 'd', 5))
+        );
+        assert_eq!(
+            from_string("HVGp3ms"),
+            Err(HebrewError::Unknown("stem", 'G', 2))
+        );
+        assert_ne!(
+            from_string("AVhp3ms"),
+            from_string("HVhp3ms").map(|p| p | ARAMAIC)
+        );
+
+        for code in [
+            "AVqp3ms",
+            "AVQp3fs",
+            "AVui3ms",
+            "AVpv2mp",
+            "AVPrmpa",
+            "AVaa",
+            "AVhc",
+            "AVsp3mp",
+            "AVeq3ms",
+            "AVHp3fs",
+            "AVti3ms",
+            "AVGp3ms",
+            "AVqsmpd",
+            "ANcfsd",
+            "ANcmpc/Sp3ms",
+            "AC/Vqw3ms",
+            "AR/Ncfsd",
+            "AAamsd",
+            "APdxcp",
+            "ANpm",
+        ] {
+            assert_eq!(
+                parse_word(code).map(|p| word_to_string(&p)),
+                Ok(code.to_string())
+            );
+        }
+        assert_eq!(
+            to_string(ARAMAIC | NOUN | COMMON_NOUN | MASCULINE | SINGULAR | DETERMINED),
+            "ANcmsd"
+        );
+    }
+}
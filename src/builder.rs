@@ -0,0 +1,368 @@
+use crate::fields::*;
+use crate::parse::*;
+use crate::parsing::Parsing;
+use crate::validate::{validate, ValidityIssue};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    // The same field was given twice.
+    Duplicate(Field),
+    // The field does not apply to the part of speech or mood.
+    Conflict(Field),
+    // The raw value is not one of the constants for the field.
+    Invalid(InvalidField),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Duplicate(field) => write!(f, "{} was set more than once", field),
+            BuildError::Conflict(field) => {
+                write!(f, "{} does not apply to this part of speech", field)
+            }
+            BuildError::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+// ParsingBuilder assembles a parsing one field at a time. Each field is
+// checked against its mask in parse.rs, and giving a field twice or
+// giving a field that the part of speech can not carry is reported by
+// build(). The result is the same u32 that from_string() would return
+// for the equivalent code.
+#[derive(Clone, Copy, Debug)]
+pub struct ParsingBuilder {
+    parsing: u32,
+    // REF_SINGULAR is zero, so a referent number that has been set can
+    // not be told apart from one that has not by the bits alone.
+    ref_number: bool,
+    error: Option<BuildError>,
+}
+
+impl ParsingBuilder {
+    pub fn new(pos: PartOfSpeech) -> ParsingBuilder {
+        ParsingBuilder {
+            parsing: u32::from(pos),
+            ref_number: false,
+            error: None,
+        }
+    }
+
+    // set stores a raw field value taken from the constants in parse.rs.
+    pub fn set(mut self, field: Field, value: u32) -> ParsingBuilder {
+        if self.error.is_some() {
+            return self;
+        }
+        if let Err(e) = check_field(field, value) {
+            self.error = Some(e);
+            return self;
+        }
+        if self.parsing & field.mask() != 0 {
+            self.error = Some(BuildError::Duplicate(field));
+            return self;
+        }
+        self.parsing |= value;
+        self
+    }
+
+    pub fn tense(self, tense: Tense) -> ParsingBuilder {
+        self.set(Field::Tense, tense.into())
+    }
+
+    pub fn voice(self, voice: Voice) -> ParsingBuilder {
+        self.set(Field::Voice, voice.into())
+    }
+
+    pub fn mood(self, mood: Mood) -> ParsingBuilder {
+        self.set(Field::Mood, mood.into())
+    }
+
+    pub fn person(self, person: Person) -> ParsingBuilder {
+        self.set(Field::Person, person.into())
+    }
+
+    pub fn case(self, case: Case) -> ParsingBuilder {
+        self.set(Field::Case, case.into())
+    }
+
+    pub fn number(self, number: Number) -> ParsingBuilder {
+        self.set(Field::Number, number.into())
+    }
+
+    pub fn gender(self, gender: Gender) -> ParsingBuilder {
+        self.set(Field::Gender, gender.into())
+    }
+
+    // ref_number sets the number of the possessor of a possessive
+    // pronoun, which is stored in the tense bits.
+    pub fn ref_number(mut self, number: Number) -> ParsingBuilder {
+        if self.error.is_some() {
+            return self;
+        }
+        if part_of_speech(self.parsing) != POSSESSIVE_PRONOUN || number == Number::Dual {
+            self.error = Some(BuildError::Conflict(Field::Number));
+            return self;
+        }
+        if self.ref_number {
+            self.error = Some(BuildError::Duplicate(Field::Number));
+            return self;
+        }
+        self.ref_number = true;
+        if number == Number::Plural {
+            self.parsing |= REF_PLURAL;
+        }
+        self
+    }
+
+    pub fn interrogative(self) -> ParsingBuilder {
+        self.flag(INTERROGATIVE)
+    }
+
+    pub fn negative(self) -> ParsingBuilder {
+        self.flag(NEGATIVE)
+    }
+
+    pub fn correlative(self) -> ParsingBuilder {
+        self.flag(CORRELATIVE)
+    }
+
+    pub fn indefinite(self) -> ParsingBuilder {
+        self.flag(INDEFINITE)
+    }
+
+    pub fn indeclinable(self) -> ParsingBuilder {
+        self.flag(INDECLINABLE)
+    }
+
+    pub fn crasis(self) -> ParsingBuilder {
+        self.flag(CRASIS)
+    }
+
+    pub fn attic(self) -> ParsingBuilder {
+        self.flag(ATTIC)
+    }
+
+    // abbreviated marks an abbreviated form. Verbs can not be abbreviated
+    // because the flag shares the tense bits.
+    pub fn abbreviated(self) -> ParsingBuilder {
+        if part_of_speech(self.parsing) == VERB {
+            return ParsingBuilder {
+                error: self.error.or(Some(BuildError::Conflict(Field::Tense))),
+                ..self
+            };
+        }
+        self.flag(ABBREVIATED)
+    }
+
+    fn flag(mut self, flag: u32) -> ParsingBuilder {
+        self.parsing |= flag;
+        self
+    }
+
+    pub fn build(self) -> Result<Parsing, BuildError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        // The parsing must agree with validate(), except that fields may
+        // be left out so that a partial parsing can still be built.
+        let p = self.parsing;
+        if let Err(issues) = validate(p) {
+            let conflict = issues.iter().find_map(|issue| match issue {
+                ValidityIssue::Missing(_) => None,
+                issue => Some(BuildError::Conflict(issue.field())),
+            });
+            if let Some(e) = conflict {
+                return Err(e);
+            }
+        }
+
+        Ok(Parsing(p))
+    }
+}
+
+fn check_field(field: Field, value: u32) -> Result<(), BuildError> {
+    let valid = match field {
+        Field::PartOfSpeech => PartOfSpeech::try_from(value).map(|_| ()),
+        Field::Tense => Tense::try_from(value).map(|_| ()),
+        Field::Voice => Voice::try_from(value).map(|_| ()),
+        Field::Mood => Mood::try_from(value).map(|_| ()),
+        Field::Person => Person::try_from(value).map(|_| ()),
+        Field::Case => Case::try_from(value).map(|_| ()),
+        Field::Number => Number::try_from(value).map(|_| ()),
+        Field::Gender => Gender::try_from(value).map(|_| ()),
+    };
+    valid.map_err(BuildError::Invalid)
+}
+
+impl Parsing {
+    pub fn builder(pos: PartOfSpeech) -> ParsingBuilder {
+        ParsingBuilder::new(pos)
+    }
+
+    pub fn verb() -> ParsingBuilder {
+        ParsingBuilder::new(PartOfSpeech::Verb)
+    }
+
+    pub fn noun() -> ParsingBuilder {
+        ParsingBuilder::new(PartOfSpeech::Noun)
+    }
+
+    pub fn adjective() -> ParsingBuilder {
+        ParsingBuilder::new(PartOfSpeech::Adjective)
+    }
+
+    pub fn article() -> ParsingBuilder {
+        ParsingBuilder::new(PartOfSpeech::Article)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_builder() {
+        let built = Parsing::verb()
+            .tense(Tense::SecondAorist)
+            .voice(Voice::Active)
+            .mood(Mood::Participle)
+            .case(Case::Accusative)
+            .number(Number::Plural)
+            .gender(Gender::Feminine)
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("V-2AAP-APF").unwrap());
+
+        let built = Parsing::verb()
+            .tense(Tense::Aorist)
+            .voice(Voice::Active)
+            .mood(Mood::Indicative)
+            .person(Person::First)
+            .number(Number::Plural)
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("V-AAI-1P").unwrap());
+
+        let built = Parsing::builder(PartOfSpeech::DemonstrativePronoun)
+            .case(Case::Accusative)
+            .number(Number::Plural)
+            .gender(Gender::Masculine)
+            .crasis()
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("D-APM-K").unwrap());
+
+        let built = Parsing::builder(PartOfSpeech::PossessivePronoun)
+            .person(Person::Second)
+            .ref_number(Number::Plural)
+            .case(Case::Accusative)
+            .number(Number::Plural)
+            .gender(Gender::Masculine)
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("S-2PAPM").unwrap());
+
+        let built = Parsing::builder(PartOfSpeech::ProperNoun)
+            .indeclinable()
+            .abbreviated()
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("N-PRI-ABB").unwrap());
+
+        let built = Parsing::verb()
+            .tense(Tense::Perfect)
+            .voice(Voice::Active)
+            .mood(Mood::Infinitive)
+            .attic()
+            .build()
+            .expect("build failed");
+        assert_eq!(built.0, from_string("V-RAN-ATT").unwrap());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_eq!(
+            Parsing::noun()
+                .case(Case::Genitive)
+                .case(Case::Dative)
+                .build(),
+            Err(BuildError::Duplicate(Field::Case))
+        );
+        assert_eq!(
+            Parsing::noun().tense(Tense::Present).build(),
+            Err(BuildError::Conflict(Field::Tense))
+        );
+        assert_eq!(
+            Parsing::verb()
+                .tense(Tense::Present)
+                .voice(Voice::Active)
+                .mood(Mood::Participle)
+                .person(Person::Third)
+                .build(),
+            Err(BuildError::Conflict(Field::Person))
+        );
+        let infinitive = Parsing::verb()
+            .tense(Tense::Present)
+            .voice(Voice::Active)
+            .mood(Mood::Infinitive);
+        assert_eq!(
+            infinitive
+                .person(Person::Third)
+                .number(Number::Singular)
+                .build(),
+            Err(BuildError::Conflict(Field::Person))
+        );
+        assert_eq!(
+            infinitive.number(Number::Singular).build(),
+            Err(BuildError::Conflict(Field::Number))
+        );
+        assert_eq!(
+            infinitive.case(Case::Nominative).build(),
+            Err(BuildError::Conflict(Field::Case))
+        );
+        assert_eq!(
+            infinitive.gender(Gender::Neuter).build(),
+            Err(BuildError::Conflict(Field::Gender))
+        );
+        assert_eq!(infinitive.build().map(|p| validate(p.bits())), Ok(Ok(())));
+        assert_eq!(
+            Parsing::noun().set(Field::Case, GENITIVE | DATIVE).build(),
+            Err(BuildError::Invalid(InvalidField(
+                Field::Case,
+                GENITIVE | DATIVE
+            )))
+        );
+        assert_eq!(
+            Parsing::noun().set(Field::Gender, NOMINATIVE).build(),
+            Err(BuildError::Invalid(InvalidField(Field::Gender, NOMINATIVE)))
+        );
+        assert_eq!(
+            Parsing::verb().abbreviated().build(),
+            Err(BuildError::Conflict(Field::Tense))
+        );
+        let possessive = Parsing::builder(PartOfSpeech::PossessivePronoun).person(Person::First);
+        assert_eq!(
+            possessive
+                .ref_number(Number::Singular)
+                .ref_number(Number::Singular)
+                .build(),
+            Err(BuildError::Duplicate(Field::Number))
+        );
+        assert_eq!(
+            possessive
+                .ref_number(Number::Singular)
+                .ref_number(Number::Plural)
+                .build(),
+            Err(BuildError::Duplicate(Field::Number))
+        );
+        assert_eq!(
+            Parsing::article().ref_number(Number::Plural).build(),
+            Err(BuildError::Conflict(Field::Number))
+        );
+    }
+}
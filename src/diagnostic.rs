@@ -0,0 +1,98 @@
+use crate::string::ParseError;
+use std::fmt;
+
+// Diagnostic renders a ParseError for a person to read. The code is
+// printed with a caret under the offending character and a note saying
+// which field the parser expected at that point. When a file name and
+// line number are given the first line starts with file:line:column so
+// that editors can jump to the mistake.
+//
+//    nestle.txt:12:5: error: unknown voice 'X'
+//      | V-AXI-3S
+//      |     ^ expected voice
+pub struct Diagnostic<'a> {
+    error: &'a ParseError,
+    file: Option<&'a str>,
+    line: Option<usize>,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(error: &'a ParseError) -> Diagnostic<'a> {
+        Diagnostic {
+            error,
+            file: None,
+            line: None,
+        }
+    }
+
+    pub fn file(mut self, file: &'a str) -> Diagnostic<'a> {
+        self.file = Some(file);
+        self
+    }
+
+    // line sets the one based line number of the code in the file.
+    pub fn line(mut self, line: usize) -> Diagnostic<'a> {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = self.error.column();
+        match (self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}:{}: ", file, line, column)?,
+            (Some(file), None) => write!(f, "{}:{}: ", file, column)?,
+            (None, Some(line)) => write!(f, "line {}, column {}: ", line, column)?,
+            (None, None) => write!(f, "column {}: ", column)?,
+        }
+        writeln!(f, "error: {}", self.error.kind.message())?;
+        writeln!(f, "  | {}", self.error.code)?;
+        write!(f, "  | {:width$}^", "", width = self.error.position)?;
+        match self.error.expected() {
+            Some(field) => write!(f, " expected {}", field),
+            None => write!(f, " unexpected character"),
+        }
+    }
+}
+
+impl ParseError {
+    pub fn diagnostic(&self) -> Diagnostic<'_> {
+        Diagnostic::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::string::*;
+
+    #[test]
+    fn test_diagnostic() {
+        let e = from_string("V-AXI-3S").expect_err("bad voice");
+        assert_eq!(
+            e.diagnostic().file("nestle.txt").line(12).to_string(),
+            "nestle.txt:12:4: error: unknown voice 'X'\n  | V-AXI-3S\n  |    ^ expected voice"
+        );
+
+        let e = from_string("N-NSX").expect_err("bad gender");
+        assert_eq!(
+            e.diagnostic().to_string(),
+            "column 5: error: unknown gender 'X'\n  | N-NSX\n  |     ^ expected gender"
+        );
+
+        let e = from_string("V-PAP-NS").expect_err("incomplete");
+        assert_eq!(
+            e.diagnostic().line(3).to_string(),
+            "line 3, column 9: error: incomplete parsing code\n  | V-PAP-NS\n  |         ^ expected gender"
+        );
+
+        let e = from_string("V-PA").expect_err("incomplete");
+        assert!(e.diagnostic().to_string().ends_with("^ expected mood"));
+
+        let e = from_string("N-NSM-Z").expect_err("bad suffix");
+        assert!(e
+            .diagnostic()
+            .to_string()
+            .ends_with("      ^ unexpected character"));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::parse::*;
+use crate::string::*;
+use crate::validate::validate;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+const TENSES: &[u32] = &[
+    PRESENT,
+    IMPERFECT,
+    FUTURE,
+    AORIST,
+    PERFECT,
+    PLUPERFECT,
+    SECOND_FUTURE,
+    SECOND_AORIST,
+    SECOND_PERFECT,
+    SECOND_PLUPERFECT,
+    FUTURE_PERFECT,
+    SECOND_FUTURE_PERFECT,
+];
+const VOICES: &[u32] = &[
+    ACTIVE_VOICE,
+    MIDDLE_VOICE,
+    PASSIVE_VOICE,
+    MIDDLE_PASSIVE_VOICE,
+    MIDDLE_DEPONENT_VOICE,
+    PASSIVE_DEPONENT_VOICE,
+    MIDDLE_PASSIVE_DEPONENT_VOICE,
+];
+const FINITE_MOODS: &[u32] = &[
+    INDICATIVE_MOOD,
+    SUBJUNCTIVE_MOOD,
+    OPTATIVE_MOOD,
+    IMPERATIVE_MOOD,
+];
+const PERSONS: &[u32] = &[FIRST_PERSON, SECOND_PERSON, THIRD_PERSON];
+const CASES: &[u32] = &[NOMINATIVE, ACCUSATIVE, GENITIVE, DATIVE, VOCATIVE];
+const NUMBERS: &[u32] = &[SINGULAR, DUAL, PLURAL];
+const GENDERS: &[u32] = &[MASCULINE, FEMININE, NEUTER];
+const FLAGS: &[u32] = &[
+    INTERROGATIVE,
+    NEGATIVE,
+    CORRELATIVE,
+    INDEFINITE,
+    INDECLINABLE,
+    CRASIS,
+];
+// Verbs only take the suffix flags.
+const VERB_FLAGS: &[u32] = &[NEGATIVE, CRASIS];
+
+// Codes that are matched whole by from_string() rather than being built
+// from fields.
+const FIXED: &[u32] = &[
+    ADVERB,
+    INTERROGATIVE | ADVERB,
+    CORRELATIVE | ADVERB,
+    NEGATIVE | ADVERB,
+    INDEFINITE | ADVERB,
+    COMPARATIVE_ADVERB,
+    SUPERLATIVE_ADVERB,
+    CONJUNCTION,
+    CONJUNCTION | CRASIS,
+    NEGATIVE | CONJUNCTION,
+    CONDITIONAL,
+    CONDITIONAL | CRASIS,
+    PARTICLE,
+    NEGATIVE | PARTICLE,
+    INTERROGATIVE | PARTICLE,
+    PREPOSITION,
+    INTERJECTION,
+    ARAMAIC_TRANSLITERATION,
+    HEBREW_TRANSLITERATION,
+    TRANSLITERATION,
+    INDECLINABLE | PROPER_NOUN,
+    INDECLINABLE | NUMERAL,
+    INDECLINABLE | LETTER,
+    INDECLINABLE | NOUN,
+    REFLEXIVE_PRONOUN,
+    POSSESSIVE_PRONOUN,
+];
+
+// Parts of speech written as a prefix followed by case, number and
+// gender. Those that may leave out the gender are marked true.
+const DECLINED: &[(u32, bool)] = &[
+    (NOUN, true),
+    (ADJECTIVE, true),
+    (PROPER_NOUN, true),
+    (ARTICLE, false),
+    (RELATIVE_PRONOUN, false),
+    (RECIPROCAL_PRONOUN, false),
+    (DEMONSTRATIVE_PRONOUN, false),
+    (PRONOUN, true),
+    (PERSONAL_PRONOUN, false),
+];
+
+// representable returns every parsing that to_string() can write and
+// from_string() reads back unchanged, in a stable order.
+pub(crate) fn representable() -> Vec<u32> {
+    let mut out = Vec::new();
+    out.extend_from_slice(FIXED);
+
+    for &tense in TENSES {
+        for &voice in VOICES {
+            let p = VERB | tense | voice;
+            for &mood in FINITE_MOODS {
+                for &person in PERSONS {
+                    for &number in NUMBERS {
+                        out.push(p | mood | person | number);
+                    }
+                }
+            }
+            out.push(p | INFINITIVE_MOOD);
+            cng(&mut out, p | PARTICIPLE_MOOD, false);
+        }
+    }
+
+    for &(p, genderless) in DECLINED {
+        cng(&mut out, p, genderless);
+    }
+    for &p in &[NOUN, ADJECTIVE] {
+        let (superlative, comparative) = if p == NOUN {
+            (SUPERLATIVE_NOUN, COMPARATIVE_NOUN)
+        } else {
+            (SUPERLATIVE_ADJECTIVE, COMPARATIVE_ADJECTIVE)
+        };
+        cng(&mut out, superlative, false);
+        cng(&mut out, comparative, false);
+    }
+    for &person in &[FIRST_PERSON, SECOND_PERSON] {
+        for &case in CASES {
+            for &number in NUMBERS {
+                out.push(PERSONAL_PRONOUN | person | case | number);
+            }
+        }
+    }
+    for &person in PERSONS {
+        cng(&mut out, REFLEXIVE_PRONOUN | person, false);
+    }
+    for &person in &[FIRST_PERSON, SECOND_PERSON] {
+        for &referent in &[REF_SINGULAR, REF_PLURAL] {
+            cng(&mut out, POSSESSIVE_PRONOUN | person | referent, false);
+        }
+    }
+
+    // Every combination of flags is tried on every parsing, and those
+    // that can not be written are dropped below. Any parsing may also
+    // be written as an attic form, and any but a verb as an abbreviated
+    // form or both. Each follows the plain parsing.
+    let flag_mask = FLAGS.iter().fold(0, |mask, flag| mask | flag);
+    let mut seen = HashSet::new();
+    let mut all = Vec::new();
+    for p in out {
+        let base = p & !flag_mask;
+        if !seen.insert(base) {
+            continue;
+        }
+        let verb = part_of_speech(base) == VERB;
+        let flags = if verb { VERB_FLAGS } else { FLAGS };
+        for subset in 0..1 << flags.len() {
+            let p = flags
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) != 0)
+                .fold(base, |p, (_, flag)| p | flag);
+            all.push(p);
+            all.push(p | ATTIC);
+            if !verb {
+                all.push(p | ABBREVIATED);
+                all.push(p | ATTIC | ABBREVIATED);
+            }
+        }
+    }
+    all.retain(|&p| from_string(&to_string(p)) == Ok(p));
+    all
+}
+
+// all_parsings returns every parsing that passes validate() and reads
+// back unchanged through to_string() and from_string(). The order is
+// stable: verbs by tense, voice and mood, then the declined parts of
+// speech by case, number and gender, with the attic and abbreviated
+// forms of each parsing straight after it.
+pub fn all_parsings() -> impl Iterator<Item = u32> {
+    valid().iter().copied()
+}
+
+// all_parsings_for returns the parsings from all_parsings() with the
+// given part of speech, such as NOUN or VERB.
+pub fn all_parsings_for(pos: u32) -> impl Iterator<Item = u32> {
+    all_parsings().filter(move |&p| part_of_speech(p) == pos)
+}
+
+fn valid() -> &'static [u32] {
+    static VALID: OnceLock<Vec<u32>> = OnceLock::new();
+    VALID.get_or_init(|| {
+        let mut all = representable();
+        all.retain(|&p| validate(p).is_ok());
+        all
+    })
+}
+
+fn cng(out: &mut Vec<u32>, p: u32, genderless: bool) {
+    for &case in CASES {
+        for &number in NUMBERS {
+            let p = p | case | number;
+            if genderless {
+                out.push(p);
+            }
+            for &gender in GENDERS {
+                out.push(p | gender);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_representable() {
+        let all = representable();
+        assert!(all.contains(&from_string("V-2AAP-APF").unwrap()));
+        assert!(all.contains(&from_string("S-2PAPM").unwrap()));
+        assert!(all.contains(&from_string("P-1AS-K").unwrap()));
+        assert!(all.contains(&from_string("N-PRI").unwrap()));
+        assert!(all.contains(&from_string("V-PAI-3D").unwrap()));
+
+        for &p in FIXED {
+            assert!(all.contains(&p), "{:#x} does not round-trip", p);
+        }
+
+        let mut sorted = all.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), all.len(), "duplicate parsings");
+    }
+
+    #[test]
+    fn test_all_parsings() {
+        for p in all_parsings() {
+            let code = to_string(p);
+            assert_eq!(from_string(&code), Ok(p), "{}", code);
+            assert_eq!(validate(p), Ok(()), "{}", code);
+        }
+        let all: Vec<u32> = all_parsings().collect();
+        assert_eq!(all, all_parsings().collect::<Vec<u32>>());
+        assert!(!all.contains(&from_string("V-PAM-1S").unwrap()));
+        assert!(!all.contains(&POSSESSIVE_PRONOUN));
+
+        let verbs: Vec<u32> = all_parsings_for(VERB).collect();
+        assert_eq!(verbs[0], from_string("V-PAI-1S").unwrap());
+        assert!(verbs.iter().all(|&p| part_of_speech(p) == VERB));
+        // Plain, crasis and negative, each with an attic form, an
+        // abbreviated form and a form that is both.
+        assert_eq!(all_parsings_for(ARTICLE).count(), 5 * 3 * 3 * 3 * 4);
+    }
+
+    #[test]
+    fn test_all_parsings_corpus() {
+        let all: HashSet<u32> = all_parsings().collect();
+        let mut missing = Vec::new();
+        for file in [
+            crate::test_case!("nestle-parsing.txt"),
+            crate::test_case!("byz-parsing.txt"),
+        ] {
+            let data = std::fs::read_to_string(file).expect("data file missing");
+            for line in data.lines() {
+                if let Ok(p) = from_string(line) {
+                    if validate(p).is_ok() && !all.contains(&p) {
+                        missing.push(line.to_string());
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "missing {:?}", missing);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::parse::*;
+use crate::string::{ParseError, ParseErrorKind};
+use std::fmt;
+
+// Field names one of the grammatical fields packed into a parsing u32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Field {
+    PartOfSpeech,
+    Tense,
+    Voice,
+    Mood,
+    Person,
+    Case,
+    Number,
+    Gender,
+}
+
+impl Field {
+    // mask returns the bits of a parsing u32 that hold this field.
+    pub fn mask(self) -> u32 {
+        match self {
+            Field::PartOfSpeech => PART_OF_SPEECH_MASK,
+            Field::Tense => TENSE_MASK,
+            Field::Voice => VOICE_MASK,
+            Field::Mood => MOOD_MASK,
+            Field::Person => PERSON_MASK,
+            Field::Case => CASE_MASK,
+            Field::Number => NUMBER_MASK,
+            Field::Gender => GENDER_MASK,
+        }
+    }
+
+    // value reads this field out of a parsing u32. Unlike masking, a
+    // non verb has no tense even when the tense bits hold other data.
+    pub fn value(self, p: u32) -> u32 {
+        match self {
+            Field::Tense => tense(p),
+            _ => p & self.mask(),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::PartOfSpeech => "part of speech",
+            Field::Tense => "tense",
+            Field::Voice => "voice",
+            Field::Mood => "mood",
+            Field::Person => "person",
+            Field::Case => "case",
+            Field::Number => "number",
+            Field::Gender => "gender",
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// InvalidField is returned when a u32 is not one of the values defined
+// for a field in parse.rs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidField(pub Field, pub u32);
+
+impl fmt::Display for InvalidField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x} is not a valid {}", self.1, self.0)
+    }
+}
+
+impl std::error::Error for InvalidField {}
+
+// Unrepresentable is reported when a value in a tag from another
+// tagging scheme has no place in a parsing u32, such as the locative
+// case. The position is the index of the value in the code that was
+// read and name is what that scheme calls the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unrepresentable {
+    pub position: usize,
+    pub value: char,
+    pub name: &'static str,
+}
+
+impl fmt::Display for Unrepresentable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' at position {} can not be represented",
+            self.name,
+            self.value,
+            self.position + 1
+        )
+    }
+}
+
+// TagReader holds what the readers for fixed width tags from other
+// tagging schemes share: the code being read, which every error quotes,
+// and the values that could not be stored. names gives what the scheme
+// calls each field, in the order the fields appear in the tag.
+pub(crate) struct TagReader {
+    code: String,
+    names: &'static [&'static str],
+    lost: Vec<Unrepresentable>,
+}
+
+impl TagReader {
+    pub(crate) fn new(code: String, names: &'static [&'static str]) -> TagReader {
+        TagReader {
+            code,
+            names,
+            lost: Vec::new(),
+        }
+    }
+
+    // fail returns the error for the character at position in the code.
+    pub(crate) fn fail(&self, kind: ParseErrorKind, position: usize) -> ParseError {
+        ParseError {
+            kind,
+            code: self.code.clone(),
+            position,
+        }
+    }
+
+    // lose reports the value of field index, found at position in the
+    // code, as one that has no place in a parsing.
+    pub(crate) fn lose(&mut self, index: usize, position: usize, value: char) {
+        self.lost.push(Unrepresentable {
+            position,
+            value,
+            name: self.names[index],
+        });
+    }
+
+    pub(crate) fn finish(self, parsing: u32) -> (u32, Vec<Unrepresentable>) {
+        (parsing, self.lost)
+    }
+}
+
+// field_enum declares an enum for one parsing field, with conversions
+// to and from the constants in parse.rs. The enum has no variant for an
+// empty field, from_parsing returns None instead.
+macro_rules! field_enum {
+    ($name:ident, $field:expr, $accessor:ident, { $($variant:ident = $value:ident, $text:expr,)* }) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            // from_parsing reads this field out of a parsing u32.
+            pub fn from_parsing(p: u32) -> Option<$name> {
+                $name::try_from($accessor(p)).ok()
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)*
+                }
+            }
+        }
+
+        impl TryFrom<u32> for $name {
+            type Error = InvalidField;
+
+            fn try_from(value: u32) -> Result<$name, InvalidField> {
+                match value {
+                    $($value => Ok($name::$variant),)*
+                    _ => Err(InvalidField($field, value)),
+                }
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(value: $name) -> u32 {
+                match value {
+                    $($name::$variant => $value,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    };
+}
+
+field_enum!(PartOfSpeech, Field::PartOfSpeech, part_of_speech, {
+    Particle = PARTICLE, "Particle",
+    Verb = VERB, "Verb",
+    Noun = NOUN, "Noun",
+    Adjective = ADJECTIVE, "Adjective",
+    Adverb = ADVERB, "Adverb",
+    Conjunction = CONJUNCTION, "Conjunction",
+    ProperNoun = PROPER_NOUN, "Proper Noun",
+    Preposition = PREPOSITION, "Preposition",
+    Conditional = CONDITIONAL, "Conditional",
+    Article = ARTICLE, "Definite Article",
+    Interjection = INTERJECTION, "Interjection",
+    Pronoun = PRONOUN, "Pronoun",
+    PersonalPronoun = PERSONAL_PRONOUN, "Personal Pronoun",
+    PossessivePronoun = POSSESSIVE_PRONOUN, "Possessive Pronoun",
+    RelativePronoun = RELATIVE_PRONOUN, "Relative Pronoun",
+    DemonstrativePronoun = DEMONSTRATIVE_PRONOUN, "Demonstrative Pronoun",
+    ReciprocalPronoun = RECIPROCAL_PRONOUN, "Reciprocal Pronoun",
+    ReflexivePronoun = REFLEXIVE_PRONOUN, "Reflexive Pronoun",
+    Transliteration = TRANSLITERATION, "Transliteration",
+    HebrewTransliteration = HEBREW_TRANSLITERATION, "Hebrew Transliteration",
+    AramaicTransliteration = ARAMAIC_TRANSLITERATION, "Aramaic Transliteration",
+    Letter = LETTER, "Letter",
+    Numeral = NUMERAL, "Numeral",
+    SuperlativeAdjective = SUPERLATIVE_ADJECTIVE, "Superlative Adjective",
+    SuperlativeAdverb = SUPERLATIVE_ADVERB, "Superlative Adverb",
+    SuperlativeNoun = SUPERLATIVE_NOUN, "Superlative Noun",
+    ComparativeAdjective = COMPARATIVE_ADJECTIVE, "Comparative Adjective",
+    ComparativeAdverb = COMPARATIVE_ADVERB, "Comparative Adverb",
+    ComparativeNoun = COMPARATIVE_NOUN, "Comparative Noun",
+});
+
+field_enum!(Tense, Field::Tense, tense, {
+    Present = PRESENT, "Present",
+    Future = FUTURE, "Future",
+    Aorist = AORIST, "Aorist",
+    Imperfect = IMPERFECT, "Imperfect",
+    Perfect = PERFECT, "Perfect",
+    Pluperfect = PLUPERFECT, "Pluperfect",
+    SecondFuture = SECOND_FUTURE, "Second Future",
+    SecondAorist = SECOND_AORIST, "Second Aorist",
+    SecondPerfect = SECOND_PERFECT, "Second Perfect",
+    SecondPluperfect = SECOND_PLUPERFECT, "Second Pluperfect",
+    FuturePerfect = FUTURE_PERFECT, "Future Perfect",
+    SecondFuturePerfect = SECOND_FUTURE_PERFECT, "Second Future Perfect",
+});
+
+field_enum!(Voice, Field::Voice, voice, {
+    Active = ACTIVE_VOICE, "Active",
+    Middle = MIDDLE_VOICE, "Middle",
+    Passive = PASSIVE_VOICE, "Passive",
+    MiddlePassive = MIDDLE_PASSIVE_VOICE, "Middle or Passive",
+    MiddleDeponent = MIDDLE_DEPONENT_VOICE, "Middle Deponent",
+    PassiveDeponent = PASSIVE_DEPONENT_VOICE, "Passive Deponent",
+    MiddlePassiveDeponent = MIDDLE_PASSIVE_DEPONENT_VOICE, "Middle or Passive Deponent",
+});
+
+field_enum!(Mood, Field::Mood, mood, {
+    Indicative = INDICATIVE_MOOD, "Indicative",
+    Subjunctive = SUBJUNCTIVE_MOOD, "Subjunctive",
+    Optative = OPTATIVE_MOOD, "Optative",
+    Imperative = IMPERATIVE_MOOD, "Imperative",
+    Infinitive = INFINITIVE_MOOD, "Infinitive",
+    Participle = PARTICIPLE_MOOD, "Participle",
+});
+
+field_enum!(Person, Field::Person, person, {
+    First = FIRST_PERSON, "First",
+    Second = SECOND_PERSON, "Second",
+    Third = THIRD_PERSON, "Third",
+});
+
+field_enum!(Case, Field::Case, case, {
+    Nominative = NOMINATIVE, "Nominative",
+    Accusative = ACCUSATIVE, "Accusative",
+    Genitive = GENITIVE, "Genitive",
+    Dative = DATIVE, "Dative",
+    Vocative = VOCATIVE, "Vocative",
+});
+
+field_enum!(Number, Field::Number, number, {
+    Singular = SINGULAR, "Singular",
+    Dual = DUAL, "Dual",
+    Plural = PLURAL, "Plural",
+});
+
+field_enum!(Gender, Field::Gender, gender, {
+    Masculine = MASCULINE, "Masculine",
+    Feminine = FEMININE, "Feminine",
+    Neuter = NEUTER, "Neuter",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_field_enums() {
+        let parsed = from_string("V-2AAP-APF").expect("parse failed");
+        assert_eq!(PartOfSpeech::from_parsing(parsed), Some(PartOfSpeech::Verb));
+        assert_eq!(Tense::from_parsing(parsed), Some(Tense::SecondAorist));
+        assert_eq!(Voice::from_parsing(parsed), Some(Voice::Active));
+        assert_eq!(Mood::from_parsing(parsed), Some(Mood::Participle));
+        assert_eq!(Person::from_parsing(parsed), None);
+        assert_eq!(Case::from_parsing(parsed), Some(Case::Accusative));
+        assert_eq!(Number::from_parsing(parsed), Some(Number::Plural));
+        assert_eq!(Gender::from_parsing(parsed), Some(Gender::Feminine));
+        assert_eq!(PartOfSpeech::from_parsing(UNKNOWN), None);
+
+        assert_eq!(Case::try_from(GENITIVE), Ok(Case::Genitive));
+        assert_eq!(
+            Case::try_from(GENITIVE | DATIVE),
+            Err(InvalidField(Field::Case, GENITIVE | DATIVE))
+        );
+        assert_eq!(
+            Gender::try_from(MASCULINE | FEMININE),
+            Err(InvalidField(Field::Gender, MASCULINE | FEMININE))
+        );
+        assert_eq!(u32::from(Voice::MiddleDeponent), MIDDLE_DEPONENT_VOICE);
+        assert_eq!(Voice::MiddleDeponent.to_string(), "Middle Deponent");
+        assert_eq!(
+            Tense::from_parsing(from_string("V-2TAI-1S").unwrap()).map(Tense::name),
+            Some("Second Future Perfect")
+        );
+    }
+
+    #[test]
+    fn test_field_enums_round_trip() {
+        for pos in PartOfSpeech::ALL {
+            assert_eq!(PartOfSpeech::from_parsing(u32::from(*pos)), Some(*pos));
+            assert_eq!(u32::from(*pos) & !Field::PartOfSpeech.mask(), 0);
+        }
+        for tense in Tense::ALL {
+            // Only verbs have a tense.
+            assert_eq!(Tense::from_parsing(VERB | u32::from(*tense)), Some(*tense));
+            assert_eq!(Tense::from_parsing(u32::from(*tense)), None);
+            assert_eq!(u32::from(*tense) & !Field::Tense.mask(), 0);
+        }
+        for voice in Voice::ALL {
+            assert_eq!(Voice::from_parsing(u32::from(*voice)), Some(*voice));
+            assert_eq!(u32::from(*voice) & !Field::Voice.mask(), 0);
+        }
+        for mood in Mood::ALL {
+            assert_eq!(Mood::from_parsing(u32::from(*mood)), Some(*mood));
+            assert_eq!(u32::from(*mood) & !Field::Mood.mask(), 0);
+        }
+        for person in Person::ALL {
+            assert_eq!(Person::from_parsing(u32::from(*person)), Some(*person));
+            assert_eq!(u32::from(*person) & !Field::Person.mask(), 0);
+        }
+        for case in Case::ALL {
+            assert_eq!(Case::from_parsing(u32::from(*case)), Some(*case));
+            assert_eq!(u32::from(*case) & !Field::Case.mask(), 0);
+        }
+        for number in Number::ALL {
+            assert_eq!(Number::from_parsing(u32::from(*number)), Some(*number));
+            assert_eq!(u32::from(*number) & !Field::Number.mask(), 0);
+        }
+        for gender in Gender::ALL {
+            assert_eq!(Gender::from_parsing(u32::from(*gender)), Some(*gender));
+            assert_eq!(u32::from(*gender) & !Field::Gender.mask(), 0);
+        }
+    }
+}
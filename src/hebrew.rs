@@ -0,0 +1,700 @@
+use crate::aramaic;
+use std::fmt;
+
+// Hebrew morphology codes from the Open Scriptures Hebrew Bible, such as
+// HVqp3ms or HTd/Ncmpa. A code starts with the language, H for Hebrew
+// or A for Aramaic, then gives one morpheme after another separated by
+// '/'. Each morpheme is a part of speech letter followed by the fields
+// for that part of speech. Aramaic has its own stems and states, which
+// are in aramaic.rs, and shares everything else with Hebrew.
+//
+// A morpheme is stored in a u32 laid out as:
+//
+//   bits 0-3    part of speech
+//   bits 4-8    type, such as common noun or definite article
+//   bits 9-14   verb stem (binyan)
+//   bits 15-18  verb conjugation
+//   bits 19-20  person
+//   bits 21-23  gender
+//   bits 24-25  number
+//   bits 26-27  state
+//   bit 28      language
+
+// Part of speech bits 0-3
+pub const UNKNOWN: u32 = 0;
+pub const ADJECTIVE: u32 = 1;
+pub const CONJUNCTION: u32 = 2;
+pub const ADVERB: u32 = 3;
+pub const NOUN: u32 = 4;
+pub const PRONOUN: u32 = 5;
+pub const PREPOSITION: u32 = 6;
+pub const SUFFIX: u32 = 7;
+pub const PARTICLE: u32 = 8;
+pub const VERB: u32 = 9;
+
+pub const PART_OF_SPEECH_MASK: u32 = 0b1111;
+
+pub fn part_of_speech(p: u32) -> u32 {
+    p & PART_OF_SPEECH_MASK
+}
+
+// Type bits 4-8. The meaning depends on the part of speech.
+pub const GENERAL_ADJECTIVE: u32 = 1 << 4;
+pub const CARDINAL_NUMBER: u32 = 2 << 4;
+pub const GENTILIC_ADJECTIVE: u32 = 3 << 4;
+pub const ORDINAL_NUMBER: u32 = 4 << 4;
+
+pub const COMMON_NOUN: u32 = 1 << 4;
+pub const GENTILIC_NOUN: u32 = 2 << 4;
+pub const PROPER_NAME: u32 = 3 << 4;
+// Written Npl, the name of a place.
+pub const LOCATION_NAME: u32 = 4 << 4;
+
+pub const DEMONSTRATIVE_PRONOUN: u32 = 1 << 4;
+pub const INDEFINITE_PRONOUN: u32 = 2 << 4;
+pub const INTERROGATIVE_PRONOUN: u32 = 3 << 4;
+pub const PERSONAL_PRONOUN: u32 = 4 << 4;
+pub const RELATIVE_PRONOUN: u32 = 5 << 4;
+
+// A preposition with the definite article merged into it.
+pub const DEFINITE_PREPOSITION: u32 = 1 << 4;
+
+pub const DIRECTIONAL_HE: u32 = 1 << 4;
+pub const PARAGOGIC_HE: u32 = 2 << 4;
+pub const PARAGOGIC_NUN: u32 = 3 << 4;
+pub const PRONOMINAL_SUFFIX: u32 = 4 << 4;
+
+pub const AFFIRMATION: u32 = 1 << 4;
+pub const DEFINITE_ARTICLE: u32 = 2 << 4;
+pub const EXHORTATION: u32 = 3 << 4;
+pub const INTERROGATIVE_PARTICLE: u32 = 4 << 4;
+pub const INTERJECTION: u32 = 5 << 4;
+pub const DEMONSTRATIVE_PARTICLE: u32 = 6 << 4;
+pub const NEGATIVE_PARTICLE: u32 = 7 << 4;
+pub const OBJECT_MARKER: u32 = 8 << 4;
+pub const RELATIVE_PARTICLE: u32 = 9 << 4;
+
+pub const TYPE_MASK: u32 = 0b11111 << 4;
+
+pub fn subtype(p: u32) -> u32 {
+    p & TYPE_MASK
+}
+
+// Verb stem bits 9-14
+pub const QAL: u32 = 1 << 9;
+pub const NIPHAL: u32 = 2 << 9;
+pub const PIEL: u32 = 3 << 9;
+pub const PUAL: u32 = 4 << 9;
+pub const HIPHIL: u32 = 5 << 9;
+pub const HOPHAL: u32 = 6 << 9;
+pub const HITHPAEL: u32 = 7 << 9;
+pub const POLEL: u32 = 8 << 9;
+pub const POLAL: u32 = 9 << 9;
+pub const HITHPOLEL: u32 = 10 << 9;
+pub const POEL: u32 = 11 << 9;
+pub const POAL: u32 = 12 << 9;
+pub const PALEL: u32 = 13 << 9;
+pub const PULAL: u32 = 14 << 9;
+pub const QAL_PASSIVE: u32 = 15 << 9;
+pub const PILPEL: u32 = 16 << 9;
+pub const POLPAL: u32 = 17 << 9;
+pub const HITHPALPEL: u32 = 18 << 9;
+pub const NITHPAEL: u32 = 19 << 9;
+pub const PEALAL: u32 = 20 << 9;
+pub const PILEL: u32 = 21 << 9;
+pub const HOTHPAAL: u32 = 22 << 9;
+pub const TIPHIL: u32 = 23 << 9;
+pub const HISHTAPHEL: u32 = 24 << 9;
+pub const NITHPALEL: u32 = 25 << 9;
+pub const NITHPOEL: u32 = 26 << 9;
+pub const HITHPOEL: u32 = 27 << 9;
+
+pub const STEM_MASK: u32 = 0b111111 << 9;
+
+pub fn stem(p: u32) -> u32 {
+    p & STEM_MASK
+}
+
+// Verb conjugation bits 15-18
+pub const PERFECT: u32 = 1 << 15;
+pub const SEQUENTIAL_PERFECT: u32 = 2 << 15;
+pub const IMPERFECT: u32 = 3 << 15;
+pub const SEQUENTIAL_IMPERFECT: u32 = 4 << 15;
+pub const COHORTATIVE: u32 = 5 << 15;
+pub const JUSSIVE: u32 = 6 << 15;
+pub const IMPERATIVE: u32 = 7 << 15;
+pub const ACTIVE_PARTICIPLE: u32 = 8 << 15;
+pub const PASSIVE_PARTICIPLE: u32 = 9 << 15;
+pub const INFINITIVE_ABSOLUTE: u32 = 10 << 15;
+pub const INFINITIVE_CONSTRUCT: u32 = 11 << 15;
+
+pub const CONJUGATION_MASK: u32 = 0b1111 << 15;
+
+pub fn conjugation(p: u32) -> u32 {
+    p & CONJUGATION_MASK
+}
+
+// Person bits 19-20
+pub const FIRST_PERSON: u32 = 1 << 19;
+pub const SECOND_PERSON: u32 = 2 << 19;
+pub const THIRD_PERSON: u32 = 3 << 19;
+
+pub const PERSON_MASK: u32 = 0b11 << 19;
+
+pub fn person(p: u32) -> u32 {
+    p & PERSON_MASK
+}
+
+// Gender bits 21-23
+pub const MASCULINE: u32 = 1 << 21;
+pub const FEMININE: u32 = 2 << 21;
+pub const BOTH_GENDERS: u32 = 3 << 21;
+pub const COMMON_GENDER: u32 = 4 << 21;
+
+pub const GENDER_MASK: u32 = 0b111 << 21;
+
+pub fn gender(p: u32) -> u32 {
+    p & GENDER_MASK
+}
+
+// Number bits 24-25
+pub const SINGULAR: u32 = 1 << 24;
+pub const PLURAL: u32 = 2 << 24;
+pub const DUAL: u32 = 3 << 24;
+
+pub const NUMBER_MASK: u32 = 0b11 << 24;
+
+pub fn number(p: u32) -> u32 {
+    p & NUMBER_MASK
+}
+
+// State bits 26-27
+pub const ABSOLUTE: u32 = 1 << 26;
+pub const CONSTRUCT: u32 = 2 << 26;
+
+pub const STATE_MASK: u32 = 0b11 << 26;
+
+pub fn state(p: u32) -> u32 {
+    p & STATE_MASK
+}
+
+// Language bit 28. Hebrew is zero. The stem and state of an Aramaic
+// parsing take the values in aramaic.rs.
+pub const ARAMAIC: u32 = 1 << 28;
+
+pub fn is_aramaic(p: u32) -> bool {
+    p & ARAMAIC == ARAMAIC
+}
+
+const PARTS_OF_SPEECH: &[(char, u32)] = &[
+    ('A', ADJECTIVE),
+    ('C', CONJUNCTION),
+    ('D', ADVERB),
+    ('N', NOUN),
+    ('P', PRONOUN),
+    ('R', PREPOSITION),
+    ('S', SUFFIX),
+    ('T', PARTICLE),
+    ('V', VERB),
+];
+const ADJECTIVE_TYPES: &[(char, u32)] = &[
+    ('a', GENERAL_ADJECTIVE),
+    ('c', CARDINAL_NUMBER),
+    ('g', GENTILIC_ADJECTIVE),
+    ('o', ORDINAL_NUMBER),
+];
+const NOUN_TYPES: &[(char, u32)] = &[('c', COMMON_NOUN), ('g', GENTILIC_NOUN), ('p', PROPER_NAME)];
+const PRONOUN_TYPES: &[(char, u32)] = &[
+    ('d', DEMONSTRATIVE_PRONOUN),
+    ('f', INDEFINITE_PRONOUN),
+    ('i', INTERROGATIVE_PRONOUN),
+    ('p', PERSONAL_PRONOUN),
+    ('r', RELATIVE_PRONOUN),
+];
+const PREPOSITION_TYPES: &[(char, u32)] = &[('d', DEFINITE_PREPOSITION)];
+const SUFFIX_TYPES: &[(char, u32)] = &[
+    ('d', DIRECTIONAL_HE),
+    ('h', PARAGOGIC_HE),
+    ('n', PARAGOGIC_NUN),
+    ('p', PRONOMINAL_SUFFIX),
+];
+const PARTICLE_TYPES: &[(char, u32)] = &[
+    ('a', AFFIRMATION),
+    ('d', DEFINITE_ARTICLE),
+    ('e', EXHORTATION),
+    ('i', INTERROGATIVE_PARTICLE),
+    ('j', INTERJECTION),
+    ('m', DEMONSTRATIVE_PARTICLE),
+    ('n', NEGATIVE_PARTICLE),
+    ('o', OBJECT_MARKER),
+    ('r', RELATIVE_PARTICLE),
+];
+const STEMS: &[(char, u32)] = &[
+    ('q', QAL),
+    ('N', NIPHAL),
+    ('p', PIEL),
+    ('P', PUAL),
+    ('h', HIPHIL),
+    ('H', HOPHAL),
+    ('t', HITHPAEL),
+    ('o', POLEL),
+    ('O', POLAL),
+    ('r', HITHPOLEL),
+    ('m', POEL),
+    ('M', POAL),
+    ('k', PALEL),
+    ('K', PULAL),
+    ('Q', QAL_PASSIVE),
+    ('l', PILPEL),
+    ('L', POLPAL),
+    ('f', HITHPALPEL),
+    ('D', NITHPAEL),
+    ('j', PEALAL),
+    ('i', PILEL),
+    ('u', HOTHPAAL),
+    ('c', TIPHIL),
+    ('v', HISHTAPHEL),
+    ('w', NITHPALEL),
+    ('y', NITHPOEL),
+    ('z', HITHPOEL),
+];
+const CONJUGATIONS: &[(char, u32)] = &[
+    ('p', PERFECT),
+    ('q', SEQUENTIAL_PERFECT),
+    ('i', IMPERFECT),
+    ('w', SEQUENTIAL_IMPERFECT),
+    ('h', COHORTATIVE),
+    ('j', JUSSIVE),
+    ('v', IMPERATIVE),
+    ('r', ACTIVE_PARTICIPLE),
+    ('s', PASSIVE_PARTICIPLE),
+    ('a', INFINITIVE_ABSOLUTE),
+    ('c', INFINITIVE_CONSTRUCT),
+];
+const PERSONS: &[(char, u32)] = &[
+    ('1', FIRST_PERSON),
+    ('2', SECOND_PERSON),
+    ('3', THIRD_PERSON),
+];
+const GENDERS: &[(char, u32)] = &[
+    ('m', MASCULINE),
+    ('f', FEMININE),
+    ('b', BOTH_GENDERS),
+    ('c', COMMON_GENDER),
+];
+const NUMBERS: &[(char, u32)] = &[('s', SINGULAR), ('p', PLURAL), ('d', DUAL)];
+const STATES: &[(char, u32)] = &[('a', ABSOLUTE), ('c', CONSTRUCT)];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HebrewError {
+    // The code ended before a field that was needed. The position is the
+    // length of the code.
+    Incomplete(usize),
+    // A character that is not a value of the named field.
+    Unknown(&'static str, char, usize),
+    // A character after the end of a morpheme.
+    Unexpected(char, usize),
+}
+
+impl HebrewError {
+    pub fn position(&self) -> usize {
+        match self {
+            HebrewError::Incomplete(position)
+            | HebrewError::Unknown(_, _, position)
+            | HebrewError::Unexpected(_, position) => *position,
+        }
+    }
+}
+
+impl fmt::Display for HebrewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HebrewError::Incomplete(_) => f.write_str("incomplete morphology code"),
+            HebrewError::Unknown(field, c, position) => {
+                write!(f, "unknown {} '{}' at column {}", field, c, position + 1)
+            }
+            HebrewError::Unexpected(c, position) => {
+                write!(f, "unexpected character '{}' at column {}", c, position + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HebrewError {}
+
+// Morpheme reads the fields of one morpheme in turn, keeping track of
+// where it is in the whole code for error positions.
+pub(crate) struct Morpheme<'a> {
+    language: u32,
+    chars: &'a [char],
+    index: usize,
+    base: usize,
+}
+
+impl<'a> Morpheme<'a> {
+    pub(crate) fn new(language: u32, chars: &'a [char], base: usize) -> Morpheme<'a> {
+        Morpheme {
+            language,
+            chars,
+            index: 0,
+            base,
+        }
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.index >= self.chars.len()
+    }
+
+    // peek returns the next character without reading it.
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    pub(crate) fn skip(&mut self) {
+        self.index += 1;
+    }
+
+    pub(crate) fn read(
+        &mut self,
+        name: &'static str,
+        table: &[(char, u32)],
+    ) -> Result<u32, HebrewError> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return Err(HebrewError::Incomplete(self.base + self.chars.len())),
+        };
+        match table.iter().find(|(letter, _)| *letter == c) {
+            Some((_, value)) => {
+                self.index += 1;
+                Ok(*value)
+            }
+            None => Err(HebrewError::Unknown(name, c, self.base + self.index)),
+        }
+    }
+
+    // person_gender_number reads the three fields that finite verbs,
+    // personal pronouns and pronominal suffixes end with.
+    pub(crate) fn person_gender_number(&mut self) -> Result<u32, HebrewError> {
+        Ok(self.read("person", PERSONS)? | self.gender_number()?)
+    }
+
+    pub(crate) fn gender_number(&mut self) -> Result<u32, HebrewError> {
+        Ok(self.read("gender", GENDERS)? | self.read("number", NUMBERS)?)
+    }
+
+    pub(crate) fn stem(&mut self) -> Result<u32, HebrewError> {
+        self.read("stem", stems(self.language))
+    }
+
+    pub(crate) fn state(&mut self) -> Result<u32, HebrewError> {
+        self.read("state", states(self.language))
+    }
+
+    pub(crate) fn finish(&self, p: u32) -> Result<u32, HebrewError> {
+        match self.peek() {
+            Some(c) => Err(HebrewError::Unexpected(c, self.base + self.index)),
+            None => Ok(p),
+        }
+    }
+}
+
+// from_string reads a code for a single morpheme, such as HNcmsa.
+pub fn from_string(code: &str) -> Result<u32, HebrewError> {
+    let chars: Vec<char> = code.chars().collect();
+    let language = language(&chars)?;
+    morpheme(language, &chars[1..], 1)
+}
+
+// parse_word reads a code for a whole word, such as HTd/Ncmpa, into
+// one value per morpheme.
+pub fn parse_word(code: &str) -> Result<Vec<u32>, HebrewError> {
+    let chars: Vec<char> = code.chars().collect();
+    let language = language(&chars)?;
+    let mut parts = Vec::new();
+    let mut base = 1;
+    for part in chars[1..].split(|c| *c == '/') {
+        parts.push(morpheme(language, part, base)?);
+        base += part.len() + 1;
+    }
+    Ok(parts)
+}
+
+fn language(chars: &[char]) -> Result<u32, HebrewError> {
+    match chars.first() {
+        Some('H') => Ok(0),
+        Some('A') => Ok(ARAMAIC),
+        Some(c) => Err(HebrewError::Unknown("language", *c, 0)),
+        None => Err(HebrewError::Incomplete(0)),
+    }
+}
+
+fn morpheme(language: u32, chars: &[char], base: usize) -> Result<u32, HebrewError> {
+    let mut m = Morpheme::new(language, chars, base);
+    let pos = m.read("part of speech", PARTS_OF_SPEECH)?;
+    let mut p = language | pos;
+    match pos {
+        ADJECTIVE => {
+            p |= m.read("adjective type", ADJECTIVE_TYPES)?;
+            p |= m.gender_number()?;
+            p |= m.state()?;
+        }
+        NOUN => {
+            p |= m.read("noun type", NOUN_TYPES)?;
+            if p & TYPE_MASK == PROPER_NAME {
+                match m.peek() {
+                    Some('l') => {
+                        m.skip();
+                        p = (p & !TYPE_MASK) | LOCATION_NAME;
+                    }
+                    Some('m' | 'f') => p |= m.read("gender", GENDERS)?,
+                    _ => {}
+                }
+            } else {
+                p |= m.gender_number()?;
+                p |= m.state()?;
+            }
+        }
+        PRONOUN => {
+            p |= m.read("pronoun type", PRONOUN_TYPES)?;
+            match p & TYPE_MASK {
+                PERSONAL_PRONOUN => p |= m.person_gender_number()?,
+                DEMONSTRATIVE_PRONOUN => {
+                    // The person of a demonstrative is always written x.
+                    m.read("person", &[('x', 0)])?;
+                    p |= m.gender_number()?;
+                }
+                _ => {}
+            }
+        }
+        PREPOSITION if !m.at_end() => p |= m.read("preposition type", PREPOSITION_TYPES)?,
+        SUFFIX => {
+            p |= m.read("suffix type", SUFFIX_TYPES)?;
+            if p & TYPE_MASK == PRONOMINAL_SUFFIX {
+                p |= m.person_gender_number()?;
+            }
+        }
+        PARTICLE => p |= m.read("particle type", PARTICLE_TYPES)?,
+        VERB => {
+            p |= m.stem()?;
+            p |= verb_form(&mut m)?;
+        }
+        _ => {}
+    }
+    m.finish(p)
+}
+
+// verb_form reads the conjugation of a verb and the fields that follow
+// it. Aramaic verbs share these with Hebrew.
+pub(crate) fn verb_form(m: &mut Morpheme) -> Result<u32, HebrewError> {
+    let conjugation = m.read("conjugation", CONJUGATIONS)?;
+    let fields = match conjugation {
+        ACTIVE_PARTICIPLE | PASSIVE_PARTICIPLE => m.gender_number()? | m.state()?,
+        INFINITIVE_ABSOLUTE | INFINITIVE_CONSTRUCT => 0,
+        _ => m.person_gender_number()?,
+    };
+    Ok(conjugation | fields)
+}
+
+// stems returns the stem letters for the language of a parsing.
+fn stems(p: u32) -> &'static [(char, u32)] {
+    if is_aramaic(p) {
+        aramaic::STEMS
+    } else {
+        STEMS
+    }
+}
+
+fn states(p: u32) -> &'static [(char, u32)] {
+    if is_aramaic(p) {
+        aramaic::STATES
+    } else {
+        STATES
+    }
+}
+
+fn letter(table: &[(char, u32)], value: u32) -> Option<char> {
+    table.iter().find(|(_, v)| *v == value).map(|(c, _)| *c)
+}
+
+fn push(s: &mut String, table: &[(char, u32)], value: u32) {
+    if let Some(c) = letter(table, value) {
+        s.push(c);
+    }
+}
+
+// to_string writes the code for a single morpheme, with the language.
+pub fn to_string(p: u32) -> String {
+    let mut s = String::from(if is_aramaic(p) { "A" } else { "H" });
+    s.push_str(&morpheme_string(p));
+    s
+}
+
+// word_to_string writes the code for a whole word from its morphemes.
+pub fn word_to_string(parts: &[u32]) -> String {
+    let mut s = String::from(match parts.first() {
+        Some(p) if is_aramaic(*p) => "A",
+        _ => "H",
+    });
+    let morphemes: Vec<String> = parts.iter().map(|p| morpheme_string(*p)).collect();
+    s.push_str(&morphemes.join("/"));
+    s
+}
+
+fn morpheme_string(p: u32) -> String {
+    let mut s = String::new();
+    let pos = part_of_speech(p);
+    push(&mut s, PARTS_OF_SPEECH, pos);
+    let kind = subtype(p);
+    match pos {
+        ADJECTIVE => {
+            push(&mut s, ADJECTIVE_TYPES, kind);
+            gender_number_string(&mut s, p);
+            push(&mut s, states(p), state(p));
+        }
+        NOUN if kind == LOCATION_NAME => s.push_str("pl"),
+        NOUN if kind == PROPER_NAME => {
+            s.push('p');
+            push(&mut s, GENDERS, gender(p));
+        }
+        NOUN => {
+            push(&mut s, NOUN_TYPES, kind);
+            gender_number_string(&mut s, p);
+            push(&mut s, states(p), state(p));
+        }
+        PRONOUN => {
+            push(&mut s, PRONOUN_TYPES, kind);
+            match kind {
+                PERSONAL_PRONOUN => person_gender_number_string(&mut s, p),
+                DEMONSTRATIVE_PRONOUN => {
+                    s.push('x');
+                    gender_number_string(&mut s, p);
+                }
+                _ => {}
+            }
+        }
+        PREPOSITION => push(&mut s, PREPOSITION_TYPES, kind),
+        SUFFIX => {
+            push(&mut s, SUFFIX_TYPES, kind);
+            if kind == PRONOMINAL_SUFFIX {
+                person_gender_number_string(&mut s, p);
+            }
+        }
+        PARTICLE => push(&mut s, PARTICLE_TYPES, kind),
+        VERB => {
+            push(&mut s, stems(p), stem(p));
+            verb_form_string(&mut s, p);
+        }
+        _ => {}
+    }
+    s
+}
+
+pub(crate) fn verb_form_string(s: &mut String, p: u32) {
+    push(s, CONJUGATIONS, conjugation(p));
+    match conjugation(p) {
+        ACTIVE_PARTICIPLE | PASSIVE_PARTICIPLE => {
+            gender_number_string(s, p);
+            push(s, states(p), state(p));
+        }
+        INFINITIVE_ABSOLUTE | INFINITIVE_CONSTRUCT => {}
+        _ => person_gender_number_string(s, p),
+    }
+}
+
+pub(crate) fn person_gender_number_string(s: &mut String, p: u32) {
+    push(s, PERSONS, person(p));
+    gender_number_string(s, p);
+}
+
+pub(crate) fn gender_number_string(s: &mut String, p: u32) {
+    push(s, GENDERS, gender(p));
+    push(s, NUMBERS, number(p));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hebrew_parse() {
+        assert_eq!(
+            from_string("HVqp3ms"),
+            Ok(VERB | QAL | PERFECT | THIRD_PERSON | MASCULINE | SINGULAR)
+        );
+        assert_eq!(
+            from_string("HNcmsa"),
+            Ok(NOUN | COMMON_NOUN | MASCULINE | SINGULAR | ABSOLUTE)
+        );
+        assert_eq!(
+            parse_word("HTd/Ncmpa"),
+            Ok(vec![
+                PARTICLE | DEFINITE_ARTICLE,
+                NOUN | COMMON_NOUN | MASCULINE | PLURAL | ABSOLUTE
+            ])
+        );
+        assert_eq!(
+            parse_word("HC/Vqw3ms/Sp3fs"),
+            Ok(vec![
+                CONJUNCTION,
+                VERB | QAL | SEQUENTIAL_IMPERFECT | THIRD_PERSON | MASCULINE | SINGULAR,
+                SUFFIX | PRONOMINAL_SUFFIX | THIRD_PERSON | FEMININE | SINGULAR
+            ])
+        );
+        assert_eq!(from_string("HNpl"), Ok(NOUN | LOCATION_NAME));
+        assert_eq!(
+            from_string("HPdxcp").map(to_string),
+            Ok("HPdxcp".to_string())
+        );
+        assert_eq!(
+            from_string("HVhrmpc"),
+            Ok(VERB | HIPHIL | ACTIVE_PARTICIPLE | MASCULINE | PLURAL | CONSTRUCT)
+        );
+        assert_eq!(word_to_string(&parse_word("HR/Ncfsc").unwrap()), "HR/Ncfsc");
+    }
+
+    // The codes in oshb-parsing.txt were entered by hand from Genesis
+    // 1:1-5 and stand in for the full morphhb inventory until that can
+    // be checked in.
+    #[test]
+    fn test_hebrew_oshb_file() {
+        let file = crate::test_case!("oshb-parsing.txt");
+        let data = std::fs::read_to_string(file);
+        if data.is_err() {
+            assert!(data.is_ok(), "data file missing. {}", file);
+        }
+        let data = data.unwrap();
+        for line in data.lines() {
+            match parse_word(line) {
+                Ok(parts) => {
+                    let stringed = word_to_string(&parts);
+                    assert_eq!(
+                        line, stringed,
+                        "loaded parsing {} but printed {}",
+                        line, stringed
+                    );
+                    if parts.len() == 1 {
+                        assert_eq!(from_string(line).map(to_string), Ok(stringed));
+                    }
+                }
+                Err(e) => panic!("Parsing {} failed. {:?}", line, e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_hebrew_errors() {
+        assert_eq!(
+            from_string("HVxp3ms"),
+            Err(HebrewError::Unknown("stem", 'x', 2))
+        );
+        assert_eq!(from_string("HNcms"), Err(HebrewError::Incomplete(5)));
+        assert_eq!(from_string("HD3"), Err(HebrewError::Unexpected('3', 2)));
+        assert_eq!(
+            from_string("GNcmsa"),
+            Err(HebrewError::Unknown("language", 'G', 0))
+        );
+        let e = parse_word("HTd/Ncmpz").unwrap_err();
+        assert_eq!(e, HebrewError::Unknown("state", 'z', 8));
+        assert_eq!(e.to_string(), "unknown state 'z' at column 9");
+    }
+}
@@ -0,0 +1,255 @@
+use crate::builder::{BuildError, ParsingBuilder};
+use crate::fields::*;
+use crate::parse::*;
+use std::fmt;
+
+// Abbreviations follow the Leipzig Glossing Rules where they have one,
+// with the usual additions for Greek such as AOR and OPT. Second tenses
+// end in 2, and deponent voices are written MDEP, PDEP and MPDEP. The
+// part of speech and the flags are not glossed, and neither is the
+// number of the possessor of a possessive pronoun, so S-2SAPM and
+// S-2PAPM both gloss as 2.ACC.PL.M and read back with a singular
+// possessor.
+const TENSES: &[(u32, &str)] = &[
+    (PRESENT, "PRS"),
+    (IMPERFECT, "IMPF"),
+    (FUTURE, "FUT"),
+    (AORIST, "AOR"),
+    (PERFECT, "PRF"),
+    (PLUPERFECT, "PLPRF"),
+    (SECOND_FUTURE, "FUT2"),
+    (SECOND_AORIST, "AOR2"),
+    (SECOND_PERFECT, "PRF2"),
+    (SECOND_PLUPERFECT, "PLPRF2"),
+    (FUTURE_PERFECT, "FUTPRF"),
+    (SECOND_FUTURE_PERFECT, "FUTPRF2"),
+];
+const VOICES: &[(u32, &str)] = &[
+    (ACTIVE_VOICE, "ACT"),
+    (MIDDLE_VOICE, "MID"),
+    (PASSIVE_VOICE, "PASS"),
+    (MIDDLE_PASSIVE_VOICE, "MP"),
+    (MIDDLE_DEPONENT_VOICE, "MDEP"),
+    (PASSIVE_DEPONENT_VOICE, "PDEP"),
+    (MIDDLE_PASSIVE_DEPONENT_VOICE, "MPDEP"),
+];
+const MOODS: &[(u32, &str)] = &[
+    (INDICATIVE_MOOD, "IND"),
+    (SUBJUNCTIVE_MOOD, "SBJV"),
+    (OPTATIVE_MOOD, "OPT"),
+    (IMPERATIVE_MOOD, "IMP"),
+    (INFINITIVE_MOOD, "INF"),
+    (PARTICIPLE_MOOD, "PTCP"),
+];
+const PERSONS: &[(u32, &str)] = &[
+    (FIRST_PERSON, "1"),
+    (SECOND_PERSON, "2"),
+    (THIRD_PERSON, "3"),
+];
+const CASES: &[(u32, &str)] = &[
+    (NOMINATIVE, "NOM"),
+    (GENITIVE, "GEN"),
+    (DATIVE, "DAT"),
+    (ACCUSATIVE, "ACC"),
+    (VOCATIVE, "VOC"),
+];
+const NUMBERS: &[(u32, &str)] = &[(SINGULAR, "SG"), (DUAL, "DU"), (PLURAL, "PL")];
+const GENDERS: &[(u32, &str)] = &[(MASCULINE, "M"), (FEMININE, "F"), (NEUTER, "N")];
+
+fn abbreviations(field: Field) -> &'static [(u32, &'static str)] {
+    match field {
+        Field::PartOfSpeech => &[],
+        Field::Tense => TENSES,
+        Field::Voice => VOICES,
+        Field::Mood => MOODS,
+        Field::Person => PERSONS,
+        Field::Case => CASES,
+        Field::Number => NUMBERS,
+        Field::Gender => GENDERS,
+    }
+}
+
+// LeipzigOptions sets the order the fields are glossed in and the
+// separator between them. A person directly followed by a number is
+// written as one gloss, such as 3SG, as the Leipzig rules suggest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeipzigOptions {
+    pub order: Vec<Field>,
+    pub separator: String,
+}
+
+impl Default for LeipzigOptions {
+    fn default() -> LeipzigOptions {
+        LeipzigOptions {
+            order: vec![
+                Field::Tense,
+                Field::Voice,
+                Field::Mood,
+                Field::Person,
+                Field::Case,
+                Field::Number,
+                Field::Gender,
+            ],
+            separator: ".".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LeipzigError {
+    UnknownAbbreviation(String),
+    Build(BuildError),
+}
+
+impl fmt::Display for LeipzigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeipzigError::UnknownAbbreviation(gloss) => {
+                write!(f, "unknown abbreviation '{}'", gloss)
+            }
+            LeipzigError::Build(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LeipzigError {}
+
+// to_leipzig glosses a parsing such as V-AAI-3S as AOR.ACT.IND.3SG.
+pub fn to_leipzig(parsed: u32) -> String {
+    to_leipzig_with(parsed, &LeipzigOptions::default())
+}
+
+pub fn to_leipzig_with(parsed: u32, opts: &LeipzigOptions) -> String {
+    let mut glosses: Vec<String> = Vec::new();
+    let mut previous = None;
+    for &field in &opts.order {
+        let value = field.value(parsed);
+        let gloss = match abbreviations(field).iter().find(|(v, _)| *v == value) {
+            Some((_, gloss)) => *gloss,
+            None => continue,
+        };
+        match glosses.last_mut() {
+            Some(last) if previous == Some(Field::Person) && field == Field::Number => {
+                last.push_str(gloss)
+            }
+            _ => glosses.push(gloss.to_string()),
+        }
+        previous = Some(field);
+    }
+    glosses.join(&opts.separator)
+}
+
+// from_leipzig reads a gloss back into a parsing for a word with the
+// given part of speech. The glosses may come in any order, and fields
+// that do not fit the part of speech are reported by the builder.
+pub fn from_leipzig(pos: PartOfSpeech, gloss: &str) -> Result<u32, LeipzigError> {
+    from_leipzig_with(pos, gloss, &LeipzigOptions::default())
+}
+
+pub fn from_leipzig_with(
+    pos: PartOfSpeech,
+    gloss: &str,
+    opts: &LeipzigOptions,
+) -> Result<u32, LeipzigError> {
+    let mut builder = ParsingBuilder::new(pos);
+    if !gloss.is_empty() {
+        for part in gloss.split(opts.separator.as_str()) {
+            builder = read_gloss(builder, part)?;
+        }
+    }
+    builder.build().map(u32::from).map_err(LeipzigError::Build)
+}
+
+fn read_gloss(mut builder: ParsingBuilder, gloss: &str) -> Result<ParsingBuilder, LeipzigError> {
+    // A person may be joined to the number that follows it.
+    if let Some(&(value, person)) = PERSONS.iter().find(|(_, p)| gloss.starts_with(p)) {
+        builder = builder.set(Field::Person, value);
+        let rest = &gloss[person.len()..];
+        if rest.is_empty() {
+            return Ok(builder);
+        }
+        return match NUMBERS.iter().find(|(_, n)| *n == rest) {
+            Some(&(value, _)) => Ok(builder.set(Field::Number, value)),
+            None => Err(LeipzigError::UnknownAbbreviation(gloss.to_string())),
+        };
+    }
+    for field in [
+        Field::Tense,
+        Field::Voice,
+        Field::Mood,
+        Field::Case,
+        Field::Number,
+        Field::Gender,
+    ] {
+        if let Some(&(value, _)) = abbreviations(field).iter().find(|(_, g)| *g == gloss) {
+            return Ok(builder.set(field, value));
+        }
+    }
+    Err(LeipzigError::UnknownAbbreviation(gloss.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_leipzig() {
+        for (code, gloss) in [
+            ("V-AAI-3S", "AOR.ACT.IND.3SG"),
+            ("V-2AAP-APF", "AOR2.ACT.PTCP.ACC.PL.F"),
+            ("V-PNM-2P", "PRS.MPDEP.IMP.2PL"),
+            ("V-RAN", "PRF.ACT.INF"),
+            ("N-GPF", "GEN.PL.F"),
+            ("P-1DS", "1.DAT.SG"),
+            ("S-2SAPM", "2.ACC.PL.M"),
+            ("ADV", ""),
+        ] {
+            let parsed = from_string(code).unwrap();
+            assert_eq!(to_leipzig(parsed), gloss, "{}", code);
+            let pos = PartOfSpeech::from_parsing(parsed).unwrap();
+            assert_eq!(from_leipzig(pos, gloss), Ok(parsed), "{}", gloss);
+        }
+
+        // The possessor number is lost.
+        let parsed = from_string("S-2PAPM").unwrap();
+        let singular = from_string("S-2SAPM").unwrap();
+        assert_ne!(parsed, singular);
+        assert_eq!(to_leipzig(parsed), "2.ACC.PL.M");
+        assert_eq!(
+            from_leipzig(PartOfSpeech::PossessivePronoun, "2.ACC.PL.M"),
+            Ok(singular)
+        );
+
+        let opts = LeipzigOptions {
+            order: vec![Field::Person, Field::Number, Field::Mood, Field::Tense],
+            separator: "-".to_string(),
+        };
+        let parsed = from_string("V-AAI-3S").unwrap();
+        assert_eq!(to_leipzig_with(parsed, &opts), "3SG-IND-AOR");
+        assert_eq!(
+            from_leipzig_with(PartOfSpeech::Verb, "3SG-IND-AOR-ACT", &opts),
+            Ok(parsed)
+        );
+    }
+
+    #[test]
+    fn test_leipzig_errors() {
+        assert_eq!(
+            from_leipzig(PartOfSpeech::Verb, "AOR.ACT.IND.3XX"),
+            Err(LeipzigError::UnknownAbbreviation("3XX".to_string()))
+        );
+        assert_eq!(
+            from_leipzig(PartOfSpeech::Noun, "GEN.PL.ACC"),
+            Err(LeipzigError::Build(BuildError::Duplicate(Field::Case)))
+        );
+        assert_eq!(
+            from_leipzig(PartOfSpeech::Noun, "AOR.GEN.PL"),
+            Err(LeipzigError::Build(BuildError::Conflict(Field::Tense)))
+        );
+        assert_eq!(
+            from_leipzig(PartOfSpeech::Verb, "PRS.ACT.PTCP.3SG"),
+            Err(LeipzigError::Build(BuildError::Conflict(Field::Person)))
+        );
+    }
+}
@@ -1,6 +1,25 @@
+pub mod agdt;
+pub mod aramaic;
+pub mod builder;
+pub mod diagnostic;
+pub mod enumerate;
+pub mod fields;
+pub mod hebrew;
+pub mod leipzig;
 mod macros;
+pub mod morphgnt;
 pub mod parse;
+pub mod parsing;
+pub mod parsing64;
+pub mod proiel;
+pub mod raw;
+pub mod segmented;
 pub mod string;
+pub mod suggest;
+pub mod ud;
+pub mod validate;
+
+pub use parsing::Parsing;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,322 @@
+use crate::parse::*;
+use crate::string::{ParseError, ParseErrorKind};
+
+// MorphGNT, as used by the SBLGNT, tags each word with two columns. The
+// first is a two character part of speech such as "V-" or "RA", and the
+// second an eight character parse, such as "3AAI-S--", that holds the
+// person, tense, voice, mood, case, number, gender and degree in that
+// order. A '-' marks an empty field.
+//
+// The MorphGNT scheme is smaller than the Robinson one, so to_codes()
+// loses detail: second tenses are written as the first tense, deponents
+// as the plain voice, proper nouns and transliterations as nouns, and
+// the pronoun classes are folded into the five MorphGNT ones.
+
+const PARSE_LENGTH: usize = 8;
+
+// from_codes reads a MorphGNT part of speech and parse into a parsing.
+// Errors report positions in the two codes joined by a space, which is
+// how they appear in the MorphGNT files.
+pub fn from_codes(pos: &str, parse: &str) -> Result<u32, ParseError> {
+    let code = format!("{} {}", pos, parse);
+    let fail = |kind: ParseErrorKind, position: usize| ParseError {
+        kind,
+        code: code.clone(),
+        position,
+    };
+
+    let mut parsing = match pos {
+        "A-" => ADJECTIVE,
+        "C-" => CONJUNCTION,
+        "D-" => ADVERB,
+        "I-" => INTERJECTION,
+        "N-" => NOUN,
+        "P-" => PREPOSITION,
+        "RA" => ARTICLE,
+        "RD" => DEMONSTRATIVE_PRONOUN,
+        "RI" => INTERROGATIVE | PRONOUN,
+        "RP" => PERSONAL_PRONOUN,
+        "RR" => RELATIVE_PRONOUN,
+        "V-" => VERB,
+        "X-" => PARTICLE,
+        _ => {
+            return Err(fail(
+                ParseErrorKind::UnknownPartOfSpeech(pos.to_string()),
+                0,
+            ))
+        }
+    };
+
+    let base = pos.chars().count() + 1;
+    let chars: Vec<char> = parse.chars().collect();
+    if chars.len() < PARSE_LENGTH {
+        return Err(fail(
+            ParseErrorKind::Incomplete(parsing),
+            base + chars.len(),
+        ));
+    }
+    if chars.len() > PARSE_LENGTH {
+        return Err(fail(
+            ParseErrorKind::UnexpectedCharacter(parsing, chars[PARSE_LENGTH]),
+            base + PARSE_LENGTH,
+        ));
+    }
+
+    let verb = parsing == VERB;
+    for (index, &c) in chars.iter().enumerate().take(PARSE_LENGTH - 1) {
+        if c == '-' {
+            continue;
+        }
+        let value = match index {
+            0 if verb || parsing == PERSONAL_PRONOUN => match c {
+                '1' => Some(FIRST_PERSON),
+                '2' => Some(SECOND_PERSON),
+                '3' => Some(THIRD_PERSON),
+                _ => return Err(fail(ParseErrorKind::UnknownPerson(parsing, c), base)),
+            },
+            1 if verb => match c {
+                'P' => Some(PRESENT),
+                'I' => Some(IMPERFECT),
+                'F' => Some(FUTURE),
+                'A' => Some(AORIST),
+                'X' => Some(PERFECT),
+                'Y' => Some(PLUPERFECT),
+                _ => {
+                    return Err(fail(
+                        ParseErrorKind::UnknownTenseForm(parsing, c),
+                        base + index,
+                    ))
+                }
+            },
+            2 if verb => match c {
+                'A' => Some(ACTIVE_VOICE),
+                'M' => Some(MIDDLE_VOICE),
+                'P' => Some(PASSIVE_VOICE),
+                _ => return Err(fail(ParseErrorKind::UnknownVoice(parsing, c), base + index)),
+            },
+            3 if verb => match c {
+                'I' => Some(INDICATIVE_MOOD),
+                'D' => Some(IMPERATIVE_MOOD),
+                'S' => Some(SUBJUNCTIVE_MOOD),
+                'O' => Some(OPTATIVE_MOOD),
+                'N' => Some(INFINITIVE_MOOD),
+                'P' => Some(PARTICIPLE_MOOD),
+                _ => return Err(fail(ParseErrorKind::UnknownMood(parsing, c), base + index)),
+            },
+            4 => match c {
+                'N' => Some(NOMINATIVE),
+                'G' => Some(GENITIVE),
+                'D' => Some(DATIVE),
+                'A' => Some(ACCUSATIVE),
+                'V' => Some(VOCATIVE),
+                _ => return Err(fail(ParseErrorKind::UnknownCase(parsing, c), base + index)),
+            },
+            5 => match c {
+                'S' => Some(SINGULAR),
+                'P' => Some(PLURAL),
+                _ => {
+                    return Err(fail(
+                        ParseErrorKind::UnknownNumber(parsing, c),
+                        base + index,
+                    ))
+                }
+            },
+            6 => match c {
+                'M' => Some(MASCULINE),
+                'F' => Some(FEMININE),
+                'N' => Some(NEUTER),
+                _ => {
+                    return Err(fail(
+                        ParseErrorKind::UnknownGender(parsing, c),
+                        base + index,
+                    ))
+                }
+            },
+            _ => None,
+        };
+        match value {
+            Some(value) => parsing |= value,
+            None => {
+                return Err(fail(
+                    ParseErrorKind::UnexpectedCharacter(parsing, c),
+                    base + index,
+                ))
+            }
+        }
+    }
+
+    let degree = match (part_of_speech(parsing), chars[PARSE_LENGTH - 1]) {
+        (_, '-') => return Ok(parsing),
+        (ADJECTIVE, 'C') => COMPARATIVE_ADJECTIVE,
+        (ADJECTIVE, 'S') => SUPERLATIVE_ADJECTIVE,
+        (NOUN, 'C') => COMPARATIVE_NOUN,
+        (NOUN, 'S') => SUPERLATIVE_NOUN,
+        (ADVERB, 'C') => COMPARATIVE_ADVERB,
+        (ADVERB, 'S') => SUPERLATIVE_ADVERB,
+        (_, c) => {
+            return Err(fail(
+                ParseErrorKind::UnexpectedCharacter(parsing, c),
+                base + PARSE_LENGTH - 1,
+            ))
+        }
+    };
+    Ok(set_part_of_speech(parsing, degree))
+}
+
+// to_codes writes a parsing as a MorphGNT part of speech and parse.
+pub fn to_codes(parsed: u32) -> (String, String) {
+    let (pos, degree) = match part_of_speech(parsed) {
+        VERB => ("V-", '-'),
+        NOUN
+        | PROPER_NOUN
+        | LETTER
+        | TRANSLITERATION
+        | HEBREW_TRANSLITERATION
+        | ARAMAIC_TRANSLITERATION => ("N-", '-'),
+        COMPARATIVE_NOUN => ("N-", 'C'),
+        SUPERLATIVE_NOUN => ("N-", 'S'),
+        ADJECTIVE | NUMERAL | POSSESSIVE_PRONOUN => ("A-", '-'),
+        COMPARATIVE_ADJECTIVE => ("A-", 'C'),
+        SUPERLATIVE_ADJECTIVE => ("A-", 'S'),
+        ADVERB => ("D-", '-'),
+        COMPARATIVE_ADVERB => ("D-", 'C'),
+        SUPERLATIVE_ADVERB => ("D-", 'S'),
+        CONJUNCTION | CONDITIONAL => ("C-", '-'),
+        PREPOSITION => ("P-", '-'),
+        INTERJECTION => ("I-", '-'),
+        ARTICLE => ("RA", '-'),
+        DEMONSTRATIVE_PRONOUN => ("RD", '-'),
+        PERSONAL_PRONOUN | REFLEXIVE_PRONOUN | RECIPROCAL_PRONOUN => ("RP", '-'),
+        RELATIVE_PRONOUN => ("RR", '-'),
+        PRONOUN => ("RI", '-'),
+        _ => ("X-", '-'),
+    };
+
+    let verb = part_of_speech(parsed) == VERB;
+    let mut s = String::with_capacity(PARSE_LENGTH);
+    s.push(match person(parsed) {
+        FIRST_PERSON if verb || pos == "RP" => '1',
+        SECOND_PERSON if verb || pos == "RP" => '2',
+        THIRD_PERSON if verb || pos == "RP" => '3',
+        _ => '-',
+    });
+    if verb {
+        s.push(match tense(parsed) {
+            PRESENT => 'P',
+            IMPERFECT => 'I',
+            FUTURE | SECOND_FUTURE | FUTURE_PERFECT | SECOND_FUTURE_PERFECT => 'F',
+            AORIST | SECOND_AORIST => 'A',
+            PERFECT | SECOND_PERFECT => 'X',
+            PLUPERFECT | SECOND_PLUPERFECT => 'Y',
+            _ => '-',
+        });
+        s.push(match voice(parsed) {
+            ACTIVE_VOICE => 'A',
+            MIDDLE_VOICE
+            | MIDDLE_PASSIVE_VOICE
+            | MIDDLE_DEPONENT_VOICE
+            | MIDDLE_PASSIVE_DEPONENT_VOICE => 'M',
+            PASSIVE_VOICE | PASSIVE_DEPONENT_VOICE => 'P',
+            _ => '-',
+        });
+        s.push(match mood(parsed) {
+            INDICATIVE_MOOD => 'I',
+            IMPERATIVE_MOOD => 'D',
+            SUBJUNCTIVE_MOOD => 'S',
+            OPTATIVE_MOOD => 'O',
+            INFINITIVE_MOOD => 'N',
+            PARTICIPLE_MOOD => 'P',
+            _ => '-',
+        });
+    } else {
+        s.push_str("---");
+    }
+    s.push(match case(parsed) {
+        NOMINATIVE => 'N',
+        GENITIVE => 'G',
+        DATIVE => 'D',
+        ACCUSATIVE => 'A',
+        VOCATIVE => 'V',
+        _ => '-',
+    });
+    // MorphGNT has no dual.
+    s.push(match number(parsed) {
+        SINGULAR => 'S',
+        PLURAL => 'P',
+        _ => '-',
+    });
+    s.push(match gender(parsed) {
+        MASCULINE => 'M',
+        FEMININE => 'F',
+        NEUTER => 'N',
+        _ => '-',
+    });
+    s.push(degree);
+
+    (pos.to_string(), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_morphgnt_codes() {
+        for (pos, parse, code) in [
+            ("V-", "3AAI-S--", "V-AAI-3S"),
+            ("V-", "-PAPNSM-", "V-PAP-NSM"),
+            ("V-", "-XMN----", "V-RMN"),
+            ("V-", "2YPD-P--", "V-LPM-2P"),
+            ("N-", "----NSF-", "N-NSF"),
+            ("A-", "----DPMC", "A-DPM-C"),
+            ("RA", "----GSN-", "T-GSN"),
+            ("RP", "----NS--", "P-NS"),
+            ("RR", "----APN-", "R-APN"),
+            ("D-", "--------", "ADV"),
+            ("D-", "-------S", "ADV-S"),
+            ("C-", "--------", "CONJ"),
+            ("X-", "--------", "PRT"),
+        ] {
+            let parsed = match from_codes(pos, parse) {
+                Ok(parsed) => parsed,
+                Err(e) => panic!("{}: {}", parse, e),
+            };
+            assert_eq!(to_string(parsed), code, "{} {}", pos, parse);
+            assert_eq!(to_codes(parsed), (pos.to_string(), parse.to_string()));
+        }
+
+        assert_eq!(
+            to_codes(from_string("V-2AMP-NPM").unwrap()),
+            ("V-".to_string(), "-AMPNPM-".to_string())
+        );
+        assert_eq!(
+            to_codes(from_string("N-PRI").unwrap()),
+            ("N-".to_string(), "--------".to_string())
+        );
+    }
+
+    #[test]
+    fn test_morphgnt_errors() {
+        let e = from_codes("V-", "3AZI-S--").unwrap_err();
+        assert_eq!(
+            e.kind,
+            ParseErrorKind::UnknownVoice(VERB | THIRD_PERSON | AORIST, 'Z')
+        );
+        assert_eq!(e.code, "V- 3AZI-S--");
+        assert_eq!(e.position, 5);
+
+        assert_eq!(
+            from_codes("ZZ", "--------").unwrap_err().kind,
+            ParseErrorKind::UnknownPartOfSpeech("ZZ".to_string())
+        );
+        assert_eq!(
+            from_codes("N-", "----NS").unwrap_err().kind,
+            ParseErrorKind::Incomplete(NOUN)
+        );
+        assert_eq!(
+            from_codes("N-", "-P--NSM-").unwrap_err().kind,
+            ParseErrorKind::UnexpectedCharacter(NOUN, 'P')
+        );
+    }
+}
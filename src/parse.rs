@@ -30,15 +30,28 @@ pub const COMPARATIVE_ADJECTIVE: u32 = 27;
 pub const COMPARATIVE_ADVERB: u32 = 28;
 pub const COMPARATIVE_NOUN: u32 = 29;
 
+pub const PART_OF_SPEECH_MASK: u32 = 0b11111;
+
 pub fn part_of_speech(p: u32) -> u32 {
-    p & 0b11111
+    p & PART_OF_SPEECH_MASK
 }
 
 pub fn set_part_of_speech(p: u32, pos: u32) -> u32 {
-    let mask = !0b11111;
+    let mask = !PART_OF_SPEECH_MASK;
     (p & mask) | pos
 }
 
+pub fn clear_part_of_speech(p: u32) -> u32 {
+    p & !PART_OF_SPEECH_MASK
+}
+
+// Attic form flag, bit 5. Robinson writes it as an -ATT suffix.
+pub const ATTIC: u32 = 1 << 5;
+
+pub fn is_attic(p: u32) -> bool {
+    p & ATTIC == ATTIC
+}
+
 // Flags that modify part of speech, bits 6-11
 pub const INTERROGATIVE: u32 = 1 << 6;
 pub const NEGATIVE: u32 = 1 << 7;
@@ -82,9 +95,38 @@ pub const SECOND_FUTURE: u32 = 7 << 12;
 pub const SECOND_AORIST: u32 = 8 << 12;
 pub const SECOND_PERFECT: u32 = 9 << 12;
 pub const SECOND_PLUPERFECT: u32 = 10 << 12;
+// Future perfect forms are not used by the Robinson codes, they are
+// written as T and 2T.
+pub const FUTURE_PERFECT: u32 = 11 << 12;
+pub const SECOND_FUTURE_PERFECT: u32 = 12 << 12;
+
+pub const TENSE_MASK: u32 = 0b1111 << 12;
 
+// tense returns the tense form of a verb. Other parts of speech use the
+// tense bits for other data, so they never have a tense.
 pub fn tense(p: u32) -> u32 {
-    p & (0b1111 << 12)
+    if part_of_speech(p) == VERB {
+        p & TENSE_MASK
+    } else {
+        0
+    }
+}
+
+// set_tense and clear_tense only change a verb, so that the referent
+// number and abbreviated flag kept in these bits on other parts of
+// speech are left alone.
+pub fn set_tense(p: u32, tense: u32) -> u32 {
+    if part_of_speech(p) != VERB {
+        return p;
+    }
+    (p & !TENSE_MASK) | (tense & TENSE_MASK)
+}
+
+pub fn clear_tense(p: u32) -> u32 {
+    if part_of_speech(p) != VERB {
+        return p;
+    }
+    p & !TENSE_MASK
 }
 
 // Tense form bits are overloaded to hold
@@ -96,13 +138,57 @@ pub fn ref_number(p: u32) -> u32 {
     p & (0b1 << 12)
 }
 
+// possessor_person returns the person of the possessor of a possessive
+// pronoun, which is kept in the person bits. S-2PAPM gives
+// SECOND_PERSON. Other parts of speech have no possessor.
+pub fn possessor_person(p: u32) -> u32 {
+    if part_of_speech(p) == POSSESSIVE_PRONOUN {
+        person(p)
+    } else {
+        0
+    }
+}
+
+// possessor_number returns SINGULAR or PLURAL for the possessor of a
+// possessive pronoun, read from the referent number in the tense bits.
+pub fn possessor_number(p: u32) -> u32 {
+    match possessor_person(p) {
+        0 => 0,
+        _ if ref_number(p) == REF_PLURAL => PLURAL,
+        _ => SINGULAR,
+    }
+}
+
+// Abbreviated forms, written with an -ABB suffix, are only found on
+// indeclinable words so the flag lives in the top tense bit. Every other
+// bit of the u32 is taken, so an abbreviated verb can not be stored:
+// on a verb this value is SECOND_AORIST, and from_string() rejects
+// -ABB after a verb with ParseErrorKind::AbbreviatedVerb. Parsing64
+// keeps the flag in a bit of its own, ABBREVIATED_FLAG, and that is
+// where it belongs once the u32 layout is retired.
+pub const ABBREVIATED: u32 = 8 << 12;
+
+pub fn is_abbreviated(p: u32) -> bool {
+    part_of_speech(p) != VERB && p & ABBREVIATED == ABBREVIATED
+}
+
 // Gender, 3 bits, 16-18
 pub const MASCULINE: u32 = 1 << 16;
 pub const FEMININE: u32 = 2 << 16;
 pub const NEUTER: u32 = 4 << 16;
 
+pub const GENDER_MASK: u32 = 0b111 << 16;
+
 pub fn gender(p: u32) -> u32 {
-    p & (0b111 << 16)
+    p & GENDER_MASK
+}
+
+pub fn set_gender(p: u32, gender: u32) -> u32 {
+    (p & !GENDER_MASK) | (gender & GENDER_MASK)
+}
+
+pub fn clear_gender(p: u32) -> u32 {
+    p & !GENDER_MASK
 }
 
 // Case, 3 bits, 19-21
@@ -112,8 +198,18 @@ pub const GENITIVE: u32 = 3 << 19;
 pub const DATIVE: u32 = 4 << 19;
 pub const VOCATIVE: u32 = 5 << 19;
 
+pub const CASE_MASK: u32 = 0b111 << 19;
+
 pub fn case(p: u32) -> u32 {
-    p & (0b111 << 19)
+    p & CASE_MASK
+}
+
+pub fn set_case(p: u32, case: u32) -> u32 {
+    (p & !CASE_MASK) | (case & CASE_MASK)
+}
+
+pub fn clear_case(p: u32) -> u32 {
+    p & !CASE_MASK
 }
 
 // Voice, 3 bits, 22-24
@@ -125,8 +221,18 @@ pub const MIDDLE_DEPONENT_VOICE: u32 = 5 << 22;
 pub const PASSIVE_DEPONENT_VOICE: u32 = 6 << 22;
 pub const MIDDLE_PASSIVE_DEPONENT_VOICE: u32 = 7 << 22;
 
+pub const VOICE_MASK: u32 = 0b111 << 22;
+
 pub fn voice(p: u32) -> u32 {
-    p & (0b111 << 22)
+    p & VOICE_MASK
+}
+
+pub fn set_voice(p: u32, voice: u32) -> u32 {
+    (p & !VOICE_MASK) | (voice & VOICE_MASK)
+}
+
+pub fn clear_voice(p: u32) -> u32 {
+    p & !VOICE_MASK
 }
 
 // Mood, 3 bits, 25-27
@@ -137,8 +243,18 @@ pub const IMPERATIVE_MOOD: u32 = 4 << 25;
 pub const INFINITIVE_MOOD: u32 = 5 << 25;
 pub const PARTICIPLE_MOOD: u32 = 6 << 25;
 
+pub const MOOD_MASK: u32 = 0b111 << 25;
+
 pub fn mood(p: u32) -> u32 {
-    p & (0b111 << 25)
+    p & MOOD_MASK
+}
+
+pub fn set_mood(p: u32, mood: u32) -> u32 {
+    (p & !MOOD_MASK) | (mood & MOOD_MASK)
+}
+
+pub fn clear_mood(p: u32) -> u32 {
+    p & !MOOD_MASK
 }
 
 // Person, 2 bits, 28-29
@@ -146,8 +262,18 @@ pub const FIRST_PERSON: u32 = 1 << 28;
 pub const SECOND_PERSON: u32 = 2 << 28;
 pub const THIRD_PERSON: u32 = 3 << 28;
 
+pub const PERSON_MASK: u32 = 0b11 << 28;
+
 pub fn person(p: u32) -> u32 {
-    p & (0b11 << 28)
+    p & PERSON_MASK
+}
+
+pub fn set_person(p: u32, person: u32) -> u32 {
+    (p & !PERSON_MASK) | (person & PERSON_MASK)
+}
+
+pub fn clear_person(p: u32) -> u32 {
+    p & !PERSON_MASK
 }
 
 // Number, 2 bits, 30-31
@@ -155,8 +281,18 @@ pub const SINGULAR: u32 = 1 << 30;
 pub const DUAL: u32 = 2 << 30;
 pub const PLURAL: u32 = 3 << 30;
 
+pub const NUMBER_MASK: u32 = 0b11 << 30;
+
 pub fn number(p: u32) -> u32 {
-    p & (0b11 << 30)
+    p & NUMBER_MASK
+}
+
+pub fn set_number(p: u32, number: u32) -> u32 {
+    (p & !NUMBER_MASK) | (number & NUMBER_MASK)
+}
+
+pub fn clear_number(p: u32) -> u32 {
+    p & !NUMBER_MASK
 }
 
 #[cfg(test)]
@@ -186,13 +322,13 @@ mod tests {
         assert_eq!(case(NEUTER | GENITIVE), GENITIVE);
 
         let parsed = DEMONSTRATIVE_PRONOUN | ACCUSATIVE | PLURAL | NEUTER;
-        assert_eq!(is_crasis(parsed), false);
+        assert!(!is_crasis(parsed));
         assert_eq!(case(parsed), ACCUSATIVE);
         assert_eq!(number(parsed), PLURAL);
         assert_eq!(gender(parsed), NEUTER);
         assert_eq!(part_of_speech(parsed), DEMONSTRATIVE_PRONOUN);
         let parsed = parsed | CRASIS;
-        assert_eq!(is_crasis(parsed), true);
+        assert!(is_crasis(parsed));
         assert_eq!(case(parsed), ACCUSATIVE);
         assert_eq!(number(parsed), PLURAL);
         assert_eq!(gender(parsed), NEUTER);
@@ -203,15 +339,87 @@ mod tests {
         );
 
         let parsed = COMPARATIVE_ADJECTIVE | INTERROGATIVE;
-        assert_eq!(is_crasis(parsed), false);
-        assert_eq!(is_interrogative(parsed), true);
+        assert!(!is_crasis(parsed));
+        assert!(is_interrogative(parsed));
 
         let parsed = COMPARATIVE_NOUN | INTERROGATIVE;
-        assert_eq!(is_crasis(parsed), false);
-        assert_eq!(is_interrogative(parsed), true);
+        assert!(!is_crasis(parsed));
+        assert!(is_interrogative(parsed));
         assert_eq!(part_of_speech(parsed), COMPARATIVE_NOUN);
     }
 
+    #[test]
+    fn test_set_fields() {
+        let parsed =
+            VERB | AORIST | ACTIVE_VOICE | PARTICIPLE_MOOD | NOMINATIVE | SINGULAR | MASCULINE;
+        let out = set_number(set_case(parsed, DATIVE), PLURAL);
+        assert_eq!(
+            out,
+            VERB | AORIST | ACTIVE_VOICE | PARTICIPLE_MOOD | DATIVE | PLURAL | MASCULINE
+        );
+        let out = set_gender(set_voice(set_tense(out, PRESENT), PASSIVE_VOICE), NEUTER);
+        assert_eq!(
+            out,
+            VERB | PRESENT | PASSIVE_VOICE | PARTICIPLE_MOOD | DATIVE | PLURAL | NEUTER
+        );
+
+        let out = set_person(
+            set_mood(clear_gender(clear_case(out)), INDICATIVE_MOOD),
+            THIRD_PERSON,
+        );
+        assert_eq!(
+            out,
+            VERB | PRESENT | PASSIVE_VOICE | INDICATIVE_MOOD | THIRD_PERSON | PLURAL
+        );
+        assert_eq!(
+            clear_person(clear_number(out)),
+            VERB | PRESENT | PASSIVE_VOICE | INDICATIVE_MOOD
+        );
+        assert_eq!(
+            clear_tense(clear_voice(clear_mood(out))),
+            VERB | THIRD_PERSON | PLURAL
+        );
+        assert_eq!(clear_part_of_speech(out) & PART_OF_SPEECH_MASK, UNKNOWN);
+
+        // Values are masked so a stray bit can not leak into another field.
+        assert_eq!(set_case(NOUN, GENITIVE | SINGULAR), NOUN | GENITIVE);
+
+        // Other parts of speech keep their own data in the tense bits.
+        let parsed = POSSESSIVE_PRONOUN | FIRST_PERSON | REF_PLURAL | DATIVE | SINGULAR;
+        assert_eq!(set_tense(parsed, AORIST), parsed);
+        assert_eq!(clear_tense(parsed), parsed);
+        let parsed = PROPER_NOUN | INDECLINABLE | ABBREVIATED;
+        assert_eq!(set_tense(parsed, PRESENT), parsed);
+        assert_eq!(clear_tense(parsed), parsed);
+    }
+
+    #[test]
+    fn test_suffix_flags() {
+        assert!(is_attic(VERB | PERFECT | ATTIC));
+        assert!(!is_attic(VERB | PERFECT));
+        assert!(is_abbreviated(PROPER_NOUN | INDECLINABLE | ABBREVIATED));
+        assert!(!is_abbreviated(PROPER_NOUN | INDECLINABLE));
+        // On a verb the same bits are a tense.
+        assert!(!is_abbreviated(VERB | SECOND_AORIST));
+    }
+
+    #[test]
+    fn test_possessor() {
+        let parsed = POSSESSIVE_PRONOUN | SECOND_PERSON | REF_PLURAL | ACCUSATIVE | PLURAL;
+        assert_eq!(tense(parsed), 0);
+        assert_eq!(possessor_person(parsed), SECOND_PERSON);
+        assert_eq!(possessor_number(parsed), PLURAL);
+        let parsed = POSSESSIVE_PRONOUN | FIRST_PERSON | NOMINATIVE | SINGULAR;
+        assert_eq!(possessor_number(parsed), SINGULAR);
+
+        // Reflexive pronouns have a person of their own, not a possessor.
+        let parsed = REFLEXIVE_PRONOUN | THIRD_PERSON | ACCUSATIVE | SINGULAR;
+        assert_eq!(possessor_person(parsed), 0);
+        assert_eq!(possessor_number(parsed), 0);
+        assert_eq!(tense(PROPER_NOUN | INDECLINABLE | ABBREVIATED), 0);
+        assert_eq!(tense(VERB | PRESENT), PRESENT);
+    }
+
     #[test]
     fn test_set_part_of_speech() {
         let parsed = NOUN; // == 3
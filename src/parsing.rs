@@ -0,0 +1,293 @@
+use crate::fields::*;
+use crate::parse::*;
+use crate::string::*;
+use std::fmt;
+use std::str::FromStr;
+
+// Parsing wraps a parsing code stored as a u32 so that it can not be
+// confused with any other integer. It has exactly the same memory
+// layout as the u32 it holds, so converting to and from the raw
+// value is free and lossless.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Parsing(pub u32);
+
+impl Parsing {
+    pub const fn new(parsing: u32) -> Parsing {
+        Parsing(parsing)
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn part_of_speech(self) -> Option<PartOfSpeech> {
+        PartOfSpeech::from_parsing(self.0)
+    }
+
+    pub fn tense(self) -> Option<Tense> {
+        Tense::from_parsing(self.0)
+    }
+
+    pub fn ref_number(self) -> u32 {
+        ref_number(self.0)
+    }
+
+    pub fn possessor_person(self) -> Option<Person> {
+        Person::try_from(possessor_person(self.0)).ok()
+    }
+
+    pub fn possessor_number(self) -> Option<Number> {
+        Number::try_from(possessor_number(self.0)).ok()
+    }
+
+    pub fn gender(self) -> Option<Gender> {
+        Gender::from_parsing(self.0)
+    }
+
+    pub fn case(self) -> Option<Case> {
+        Case::from_parsing(self.0)
+    }
+
+    pub fn voice(self) -> Option<Voice> {
+        Voice::from_parsing(self.0)
+    }
+
+    pub fn mood(self) -> Option<Mood> {
+        Mood::from_parsing(self.0)
+    }
+
+    pub fn person(self) -> Option<Person> {
+        Person::from_parsing(self.0)
+    }
+
+    pub fn number(self) -> Option<Number> {
+        Number::from_parsing(self.0)
+    }
+
+    pub fn is_interrogative(self) -> bool {
+        is_interrogative(self.0)
+    }
+
+    pub fn is_negative(self) -> bool {
+        is_negative(self.0)
+    }
+
+    pub fn is_correlative(self) -> bool {
+        is_correlative(self.0)
+    }
+
+    pub fn is_indefinite(self) -> bool {
+        is_indefinite(self.0)
+    }
+
+    pub fn is_indeclinable(self) -> bool {
+        is_indeclinable(self.0)
+    }
+
+    pub fn is_crasis(self) -> bool {
+        is_crasis(self.0)
+    }
+
+    pub fn is_attic(self) -> bool {
+        is_attic(self.0)
+    }
+
+    pub fn is_abbreviated(self) -> bool {
+        is_abbreviated(self.0)
+    }
+
+    pub fn with_part_of_speech(self, part_of_speech: PartOfSpeech) -> Parsing {
+        Parsing(set_part_of_speech(self.0, part_of_speech.into()))
+    }
+
+    pub fn without_part_of_speech(self) -> Parsing {
+        Parsing(clear_part_of_speech(self.0))
+    }
+
+    pub fn with_tense(self, tense: Tense) -> Parsing {
+        Parsing(set_tense(self.0, tense.into()))
+    }
+
+    pub fn without_tense(self) -> Parsing {
+        Parsing(clear_tense(self.0))
+    }
+
+    pub fn with_gender(self, gender: Gender) -> Parsing {
+        Parsing(set_gender(self.0, gender.into()))
+    }
+
+    pub fn without_gender(self) -> Parsing {
+        Parsing(clear_gender(self.0))
+    }
+
+    pub fn with_case(self, case: Case) -> Parsing {
+        Parsing(set_case(self.0, case.into()))
+    }
+
+    pub fn without_case(self) -> Parsing {
+        Parsing(clear_case(self.0))
+    }
+
+    pub fn with_voice(self, voice: Voice) -> Parsing {
+        Parsing(set_voice(self.0, voice.into()))
+    }
+
+    pub fn without_voice(self) -> Parsing {
+        Parsing(clear_voice(self.0))
+    }
+
+    pub fn with_mood(self, mood: Mood) -> Parsing {
+        Parsing(set_mood(self.0, mood.into()))
+    }
+
+    pub fn without_mood(self) -> Parsing {
+        Parsing(clear_mood(self.0))
+    }
+
+    pub fn with_person(self, person: Person) -> Parsing {
+        Parsing(set_person(self.0, person.into()))
+    }
+
+    pub fn without_person(self) -> Parsing {
+        Parsing(clear_person(self.0))
+    }
+
+    pub fn with_number(self, number: Number) -> Parsing {
+        Parsing(set_number(self.0, number.into()))
+    }
+
+    pub fn without_number(self) -> Parsing {
+        Parsing(clear_number(self.0))
+    }
+}
+
+impl From<u32> for Parsing {
+    fn from(parsing: u32) -> Parsing {
+        Parsing(parsing)
+    }
+}
+
+impl From<Parsing> for u32 {
+    fn from(parsing: Parsing) -> u32 {
+        parsing.0
+    }
+}
+
+impl FromStr for Parsing {
+    type Err = ParseError;
+
+    fn from_str(code: &str) -> Result<Parsing, ParseError> {
+        from_string(code).map(Parsing)
+    }
+}
+
+impl fmt::Display for Parsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_string(self.0))
+    }
+}
+
+impl fmt::Debug for Parsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parsing")
+            .field("code", &to_string(self.0))
+            .field("part_of_speech", &self.part_of_speech())
+            .field("tense", &self.tense())
+            .field("voice", &self.voice())
+            .field("mood", &self.mood())
+            .field("person", &self.person())
+            .field("case", &self.case())
+            .field("number", &self.number())
+            .field("gender", &self.gender())
+            .field("bits", &format_args!("{:#034b}", self.0))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_round_trip() {
+        let parsed: Parsing = "V-2AAP-APF".parse().expect("parse failed");
+        assert_eq!(
+            parsed,
+            Parsing(
+                VERB | SECOND_AORIST
+                    | ACTIVE_VOICE
+                    | PARTICIPLE_MOOD
+                    | ACCUSATIVE
+                    | PLURAL
+                    | FEMININE
+            )
+        );
+        assert_eq!(parsed.to_string(), "V-2AAP-APF");
+        assert_eq!(parsed.tense(), Some(Tense::SecondAorist));
+        assert_eq!(parsed.mood(), Some(Mood::Participle));
+        assert_eq!(parsed.case(), Some(Case::Accusative));
+        assert_eq!(parsed.person(), None);
+        assert!(!parsed.is_crasis());
+
+        let raw: u32 = parsed.into();
+        assert_eq!(Parsing::from(raw), parsed);
+        assert_eq!(raw, from_string("V-2AAP-APF").unwrap());
+        assert_eq!(std::mem::size_of::<Parsing>(), std::mem::size_of::<u32>());
+
+        let parsed: Parsing = "S-2PAPM".parse().unwrap();
+        assert_eq!(parsed.tense(), None);
+        assert_eq!(parsed.possessor_person(), Some(Person::Second));
+        assert_eq!(parsed.possessor_number(), Some(Number::Plural));
+        assert_eq!(parsed.to_string(), "S-2PAPM");
+        let parsed: Parsing = "F-3ASM".parse().unwrap();
+        assert_eq!(parsed.person(), Some(Person::Third));
+        assert_eq!(parsed.possessor_person(), None);
+        assert_eq!(parsed.to_string(), "F-3ASM");
+
+        assert_eq!(
+            "Z-APM".parse::<Parsing>().unwrap_err().kind,
+            ParseErrorKind::UnknownPartOfSpeech("Z".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parsing_with() {
+        let parsed: Parsing = "V-PAP-NSM".parse().unwrap();
+        let drill = parsed.with_case(Case::Dative).with_number(Number::Plural);
+        assert_eq!(drill.to_string(), "V-PAP-DPM");
+        assert_eq!(drill.with_tense(Tense::Aorist).to_string(), "V-AAP-DPM");
+        assert_eq!(
+            drill
+                .with_mood(Mood::Indicative)
+                .without_case()
+                .without_gender()
+                .with_person(Person::Third)
+                .to_string(),
+            "V-PAI-3P"
+        );
+        assert_eq!(
+            drill
+                .without_tense()
+                .without_voice()
+                .without_mood()
+                .with_part_of_speech(PartOfSpeech::Adjective)
+                .to_string(),
+            "A-DPM"
+        );
+        let possessive: Parsing = "S-2PAPM".parse().unwrap();
+        assert_eq!(possessive.without_tense(), possessive);
+        let abbreviated: Parsing = "N-PRI-ABB".parse().unwrap();
+        assert_eq!(abbreviated.with_tense(Tense::Aorist), abbreviated);
+    }
+
+    #[test]
+    fn test_parsing_debug() {
+        let parsed = Parsing(DEMONSTRATIVE_PRONOUN | ACCUSATIVE | PLURAL | MASCULINE | CRASIS);
+        let debug = format!("{:?}", parsed);
+        assert!(debug.contains("\"D-APM-K\""), "{}", debug);
+        assert!(debug.contains("DemonstrativePronoun"), "{}", debug);
+        assert!(debug.contains("Some(Accusative)"), "{}", debug);
+        assert!(debug.contains("tense: None"), "{}", debug);
+    }
+}
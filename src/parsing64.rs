@@ -0,0 +1,373 @@
+use crate::parse::*;
+use crate::parsing::Parsing;
+use std::fmt;
+
+// Parsing64 is a wider parsing with room for the data that the u32
+// layout in parse.rs has to squeeze into fields meant for something
+// else. The low 32 bits keep the u32 layout, except that:
+//
+//   the tense bits 12-15 are only used by verbs,
+//   the person bits of a possessive pronoun are not used,
+//   bit 5, the attic flag, is not used.
+//
+// The rest is laid out as:
+//
+//   bits 32-34  language
+//   bits 35-36  possessor person
+//   bits 37-38  possessor number
+//   bits 39-42  dialect
+//   bits 43-48  Hebrew or Aramaic verb stem
+//   bits 49-50  Hebrew or Aramaic state
+//   bit 51      abbreviated
+//   bits 52-63  spare flags
+//
+// Any u32 converts to a Parsing64 and back without loss. A Parsing64
+// only converts to a u32 when everything it holds has a place there.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Parsing64(pub u64);
+
+// Language bits 32-34
+pub const GREEK: u64 = 0;
+pub const HEBREW: u64 = 1 << 32;
+pub const ARAMAIC: u64 = 2 << 32;
+
+pub const LANGUAGE_MASK: u64 = 0b111 << 32;
+
+// Possessor person bits 35-36 and number bits 37-38 hold the person
+// and number values of parse.rs shifted up.
+const POSSESSOR_PERSON_SHIFT: u32 = 35 - 28;
+const POSSESSOR_NUMBER_SHIFT: u32 = 37 - 30;
+
+pub const POSSESSOR_PERSON_MASK: u64 = 0b11 << 35;
+pub const POSSESSOR_NUMBER_MASK: u64 = 0b11 << 37;
+
+// Dialect bits 39-42
+pub const ATTIC_DIALECT: u64 = 1 << 39;
+pub const IONIC_DIALECT: u64 = 2 << 39;
+pub const DORIC_DIALECT: u64 = 3 << 39;
+pub const AEOLIC_DIALECT: u64 = 4 << 39;
+
+pub const DIALECT_MASK: u64 = 0b1111 << 39;
+
+// Stem bits 43-48 and state bits 49-50 hold the stem and state values
+// of hebrew.rs and aramaic.rs shifted up.
+const STEM_SHIFT: u32 = 43 - 9;
+const STATE_SHIFT: u32 = 49 - 26;
+
+pub const STEM_MASK: u64 = 0b111111 << 43;
+pub const STATE_MASK: u64 = 0b11 << 49;
+
+pub const ABBREVIATED_FLAG: u64 = 1 << 51;
+
+pub const FLAGS_MASK: u64 = 0xfff << 52;
+
+impl Parsing64 {
+    pub const fn new(parsing: u64) -> Parsing64 {
+        Parsing64(parsing)
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    // low returns the bits that share the u32 layout.
+    pub fn low(self) -> u32 {
+        self.0 as u32
+    }
+
+    pub fn language(self) -> u64 {
+        self.0 & LANGUAGE_MASK
+    }
+
+    pub fn with_language(self, language: u64) -> Parsing64 {
+        Parsing64((self.0 & !LANGUAGE_MASK) | (language & LANGUAGE_MASK))
+    }
+
+    // possessor_person returns the person of the possessor of a
+    // possessive pronoun, as one of the person values in parse.rs.
+    pub fn possessor_person(self) -> u32 {
+        ((self.0 & POSSESSOR_PERSON_MASK) >> POSSESSOR_PERSON_SHIFT) as u32
+    }
+
+    pub fn possessor_number(self) -> u32 {
+        ((self.0 & POSSESSOR_NUMBER_MASK) >> POSSESSOR_NUMBER_SHIFT) as u32
+    }
+
+    pub fn with_possessor(self, person: u32, number: u32) -> Parsing64 {
+        let person = ((person & PERSON_MASK) as u64) << POSSESSOR_PERSON_SHIFT;
+        let number = ((number & NUMBER_MASK) as u64) << POSSESSOR_NUMBER_SHIFT;
+        Parsing64((self.0 & !(POSSESSOR_PERSON_MASK | POSSESSOR_NUMBER_MASK)) | person | number)
+    }
+
+    pub fn dialect(self) -> u64 {
+        self.0 & DIALECT_MASK
+    }
+
+    pub fn with_dialect(self, dialect: u64) -> Parsing64 {
+        Parsing64((self.0 & !DIALECT_MASK) | (dialect & DIALECT_MASK))
+    }
+
+    // stem returns the stem as one of the stem values in hebrew.rs or
+    // aramaic.rs, depending on the language.
+    pub fn stem(self) -> u32 {
+        ((self.0 & STEM_MASK) >> STEM_SHIFT) as u32
+    }
+
+    pub fn with_stem(self, stem: u32) -> Parsing64 {
+        let stem = ((stem & crate::hebrew::STEM_MASK) as u64) << STEM_SHIFT;
+        Parsing64((self.0 & !STEM_MASK) | stem)
+    }
+
+    pub fn state(self) -> u32 {
+        ((self.0 & STATE_MASK) >> STATE_SHIFT) as u32
+    }
+
+    pub fn with_state(self, state: u32) -> Parsing64 {
+        let state = ((state & crate::hebrew::STATE_MASK) as u64) << STATE_SHIFT;
+        Parsing64((self.0 & !STATE_MASK) | state)
+    }
+
+    pub fn is_abbreviated(self) -> bool {
+        self.0 & ABBREVIATED_FLAG == ABBREVIATED_FLAG
+    }
+
+    // flags returns the twelve spare flag bits, which this crate does
+    // not use, so that callers can annotate parsings.
+    pub fn flags(self) -> u16 {
+        ((self.0 & FLAGS_MASK) >> 52) as u16
+    }
+
+    pub fn with_flags(self, flags: u16) -> Parsing64 {
+        Parsing64((self.0 & !FLAGS_MASK) | (((flags as u64) << 52) & FLAGS_MASK))
+    }
+}
+
+impl From<u32> for Parsing64 {
+    fn from(p: u32) -> Parsing64 {
+        let mut low = p;
+        let mut high = 0;
+        if is_attic(p) {
+            low &= !ATTIC;
+            high |= ATTIC_DIALECT;
+        }
+        if part_of_speech(p) != VERB {
+            if p & REF_PLURAL == REF_PLURAL {
+                low &= !REF_PLURAL;
+                high |= (PLURAL as u64) << POSSESSOR_NUMBER_SHIFT;
+            }
+            if is_abbreviated(p) {
+                low &= !ABBREVIATED;
+                high |= ABBREVIATED_FLAG;
+            }
+        }
+        if part_of_speech(p) == POSSESSIVE_PRONOUN && person(p) != 0 {
+            low &= !PERSON_MASK;
+            high |= (person(p) as u64) << POSSESSOR_PERSON_SHIFT;
+            if high & POSSESSOR_NUMBER_MASK == 0 {
+                high |= (SINGULAR as u64) << POSSESSOR_NUMBER_SHIFT;
+            }
+        }
+        Parsing64(high | low as u64)
+    }
+}
+
+impl From<Parsing> for Parsing64 {
+    fn from(p: Parsing) -> Parsing64 {
+        Parsing64::from(p.0)
+    }
+}
+
+// TruncateError names the field of a Parsing64 that has no place in a
+// u32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateError {
+    Language,
+    Possessor,
+    Dialect,
+    Stem,
+    State,
+    Abbreviated,
+    Flags,
+}
+
+impl TruncateError {
+    pub fn name(self) -> &'static str {
+        match self {
+            TruncateError::Language => "language",
+            TruncateError::Possessor => "possessor",
+            TruncateError::Dialect => "dialect",
+            TruncateError::Stem => "stem",
+            TruncateError::State => "state",
+            TruncateError::Abbreviated => "abbreviated flag",
+            TruncateError::Flags => "flags",
+        }
+    }
+}
+
+impl fmt::Display for TruncateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} can not be stored in 32 bits", self.name())
+    }
+}
+
+impl std::error::Error for TruncateError {}
+
+impl TryFrom<Parsing64> for u32 {
+    type Error = TruncateError;
+
+    fn try_from(p: Parsing64) -> Result<u32, TruncateError> {
+        let mut low = p.low();
+        let verb = part_of_speech(low) == VERB;
+        if p.language() != GREEK {
+            return Err(TruncateError::Language);
+        }
+        if p.stem() != 0 {
+            return Err(TruncateError::Stem);
+        }
+        if p.state() != 0 {
+            return Err(TruncateError::State);
+        }
+        if p.flags() != 0 {
+            return Err(TruncateError::Flags);
+        }
+        match p.dialect() {
+            0 => {}
+            ATTIC_DIALECT => low |= ATTIC,
+            _ => return Err(TruncateError::Dialect),
+        }
+        if p.is_abbreviated() {
+            if verb {
+                return Err(TruncateError::Abbreviated);
+            }
+            low |= ABBREVIATED;
+        }
+
+        // Only a possessive pronoun has a place for the person of its
+        // possessor, and a singular possessor is only written with a
+        // person.
+        let possessor_person = p.possessor_person();
+        if possessor_person != 0 {
+            if part_of_speech(low) != POSSESSIVE_PRONOUN || person(low) != 0 {
+                return Err(TruncateError::Possessor);
+            }
+            low |= possessor_person;
+        }
+        match p.possessor_number() {
+            0 => {}
+            SINGULAR if possessor_person != 0 => {}
+            PLURAL if !verb => low |= REF_PLURAL,
+            _ => return Err(TruncateError::Possessor),
+        }
+        Ok(low)
+    }
+}
+
+impl TryFrom<Parsing64> for Parsing {
+    type Error = TruncateError;
+
+    fn try_from(p: Parsing64) -> Result<Parsing, TruncateError> {
+        u32::try_from(p).map(Parsing)
+    }
+}
+
+impl fmt::Debug for Parsing64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parsing64")
+            .field("low", &Parsing(self.low()))
+            .field("language", &(self.language() >> 32))
+            .field("possessor_person", &(self.possessor_person() >> 28))
+            .field("possessor_number", &(self.possessor_number() >> 30))
+            .field("dialect", &(self.dialect() >> 39))
+            .field("stem", &(self.stem() >> 9))
+            .field("state", &(self.state() >> 26))
+            .field("abbreviated", &self.is_abbreviated())
+            .field("flags", &format_args!("{:#014b}", self.flags()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_parsing64() {
+        let p = Parsing64::from(from_string("S-2PAPM").unwrap());
+        assert_eq!(p.possessor_person(), SECOND_PERSON);
+        assert_eq!(p.possessor_number(), PLURAL);
+        assert_eq!(tense(p.low()), 0);
+        assert_eq!(person(p.low()), 0);
+        assert_eq!(
+            p.low(),
+            POSSESSIVE_PRONOUN | ACCUSATIVE | PLURAL | MASCULINE
+        );
+
+        let p = Parsing64::from(from_string("S-1SNSF").unwrap());
+        assert_eq!(p.possessor_person(), FIRST_PERSON);
+        assert_eq!(p.possessor_number(), SINGULAR);
+
+        let p = Parsing64::from(VERB | PERFECT | ATTIC);
+        assert_eq!(p.dialect(), ATTIC_DIALECT);
+        assert_eq!(p.low(), VERB | PERFECT);
+
+        let p = Parsing64::from(PROPER_NOUN | INDECLINABLE | ABBREVIATED);
+        assert!(p.is_abbreviated());
+        assert_eq!(p.low(), PROPER_NOUN | INDECLINABLE);
+
+        let p = Parsing64::default()
+            .with_language(HEBREW)
+            .with_stem(crate::hebrew::HIPHIL)
+            .with_state(crate::hebrew::CONSTRUCT)
+            .with_flags(0b101);
+        assert_eq!(p.language(), HEBREW);
+        assert_eq!(p.stem(), crate::hebrew::HIPHIL);
+        assert_eq!(p.state(), crate::hebrew::CONSTRUCT);
+        assert_eq!(p.flags(), 0b101);
+        assert_eq!(u32::try_from(p), Err(TruncateError::Language));
+    }
+
+    #[test]
+    fn test_parsing64_round_trip() {
+        for file in [
+            crate::test_case!("nestle-parsing.txt"),
+            crate::test_case!("byz-parsing.txt"),
+        ] {
+            let data = std::fs::read_to_string(file).expect("data file missing");
+            for line in data.lines() {
+                if let Ok(p) = from_string(line) {
+                    assert_eq!(u32::try_from(Parsing64::from(p)), Ok(p), "{}", line);
+                }
+            }
+        }
+        for p in [u32::MAX, 0, REF_PLURAL, ATTIC | VERB | ABBREVIATED] {
+            assert_eq!(u32::try_from(Parsing64::from(p)), Ok(p), "{:#x}", p);
+        }
+
+        let noun = Parsing64::from(NOUN | NOMINATIVE | SINGULAR | FEMININE);
+        for (p, e) in [
+            (noun.with_dialect(DORIC_DIALECT), TruncateError::Dialect),
+            (
+                noun.with_possessor(FIRST_PERSON, SINGULAR),
+                TruncateError::Possessor,
+            ),
+            (noun.with_stem(crate::aramaic::PEAL), TruncateError::Stem),
+            (
+                noun.with_state(crate::aramaic::DETERMINED),
+                TruncateError::State,
+            ),
+            (noun.with_flags(1), TruncateError::Flags),
+            (
+                Parsing64(Parsing64::from(VERB | AORIST).0 | ABBREVIATED_FLAG),
+                TruncateError::Abbreviated,
+            ),
+        ] {
+            assert_eq!(u32::try_from(p), Err(e));
+        }
+        assert_eq!(
+            TruncateError::Possessor.to_string(),
+            "possessor can not be stored in 32 bits"
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use crate::fields::{TagReader, Unrepresentable};
+use crate::parse::*;
+use crate::string::{ParseError, ParseErrorKind};
+
+// The PROIEL treebank tags each word with a two character part of
+// speech, such as "V-", "Nb" or "Pp", and a ten character morphology,
+// such as "3sria----i", that holds the person, number, tense, mood,
+// voice, gender, case, degree, strength and inflection in that order.
+// A '-' marks an empty field.
+//
+// PROIEL covers Latin, Gothic and other languages as well as Greek, so
+// several of its values have no place in a parsing: uncertain 'x'
+// values, merged genders and cases, the Latin moods, and the Germanic
+// strength field. from_codes() reports these rather than failing. The
+// non-inflecting mark is kept as the indeclinable flag on nouns and
+// numerals and is implied for every other part of speech.
+//
+// Parts of speech are mapped to the nearest Robinson one. Relative
+// adverbs, subjunctions, ordinals and quantifiers have no class of
+// their own and are read as adverbs, conjunctions and adjectives.
+
+const MORPHOLOGY_LENGTH: usize = 10;
+
+const NAMES: &[&str; MORPHOLOGY_LENGTH] = &[
+    "person",
+    "number",
+    "tense",
+    "mood",
+    "voice",
+    "gender",
+    "case",
+    "degree",
+    "strength",
+    "inflection",
+];
+
+// from_codes reads a PROIEL part of speech and morphology into a
+// parsing, with the values that could not be stored. Positions count
+// characters in the two codes joined by a space.
+pub fn from_codes(pos: &str, morphology: &str) -> Result<(u32, Vec<Unrepresentable>), ParseError> {
+    let mut reader = TagReader::new(format!("{} {}", pos, morphology), NAMES);
+
+    let mut parsing = match pos {
+        "A-" | "Mo" | "Py" => ADJECTIVE,
+        "C-" | "G-" => CONJUNCTION,
+        "Df" | "Dq" => ADVERB,
+        "Du" => INTERROGATIVE | ADVERB,
+        "F-" => TRANSLITERATION,
+        "I-" => INTERJECTION,
+        "Ma" => NUMERAL,
+        "Nb" => NOUN,
+        "Ne" => PROPER_NOUN,
+        "Pc" => RECIPROCAL_PRONOUN,
+        "Pd" => DEMONSTRATIVE_PRONOUN,
+        "Pi" => INTERROGATIVE | PRONOUN,
+        "Pk" => REFLEXIVE_PRONOUN,
+        "Pp" => PERSONAL_PRONOUN,
+        "Pr" => RELATIVE_PRONOUN,
+        "Ps" | "Pt" => POSSESSIVE_PRONOUN,
+        "Px" => INDEFINITE | PRONOUN,
+        "R-" => PREPOSITION,
+        "S-" => ARTICLE,
+        "V-" => VERB,
+        "X-" => UNKNOWN,
+        _ => return Err(reader.fail(ParseErrorKind::UnknownPartOfSpeech(pos.to_string()), 0)),
+    };
+
+    let base = pos.chars().count() + 1;
+    let chars: Vec<char> = morphology.chars().collect();
+    if chars.len() < MORPHOLOGY_LENGTH {
+        return Err(reader.fail(ParseErrorKind::Incomplete(parsing), base + chars.len()));
+    }
+    if chars.len() > MORPHOLOGY_LENGTH {
+        return Err(reader.fail(
+            ParseErrorKind::UnexpectedCharacter(parsing, chars[MORPHOLOGY_LENGTH]),
+            base + MORPHOLOGY_LENGTH,
+        ));
+    }
+
+    let pos = part_of_speech(parsing);
+    let verb = pos == VERB;
+    let has_person = matches!(
+        pos,
+        VERB | REFLEXIVE_PRONOUN | PERSONAL_PRONOUN | POSSESSIVE_PRONOUN
+    );
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '-' {
+            continue;
+        }
+        let at = base + index;
+        // None marks a known PROIEL value that a parsing can not hold.
+        let value = match index {
+            0 => match c {
+                '1' => Some(FIRST_PERSON),
+                '2' => Some(SECOND_PERSON),
+                '3' => Some(THIRD_PERSON),
+                'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownPerson(parsing, c), at)),
+            },
+            1 => match c {
+                's' => Some(SINGULAR),
+                'd' => Some(DUAL),
+                'p' => Some(PLURAL),
+                'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownNumber(parsing, c), at)),
+            },
+            2 => match c {
+                'p' => Some(PRESENT),
+                'i' => Some(IMPERFECT),
+                'r' => Some(PERFECT),
+                'l' => Some(PLUPERFECT),
+                't' => Some(FUTURE_PERFECT),
+                'f' => Some(FUTURE),
+                'a' => Some(AORIST),
+                'u' | 's' | 'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownTenseForm(parsing, c), at)),
+            },
+            3 => match c {
+                'i' => Some(INDICATIVE_MOOD),
+                's' => Some(SUBJUNCTIVE_MOOD),
+                'm' => Some(IMPERATIVE_MOOD),
+                'o' => Some(OPTATIVE_MOOD),
+                'n' => Some(INFINITIVE_MOOD),
+                'p' => Some(PARTICIPLE_MOOD),
+                'd' | 'g' | 'u' | 'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownMood(parsing, c), at)),
+            },
+            4 => match c {
+                'a' => Some(ACTIVE_VOICE),
+                'p' => Some(PASSIVE_VOICE),
+                'm' => Some(MIDDLE_VOICE),
+                'e' => Some(MIDDLE_PASSIVE_VOICE),
+                'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownVoice(parsing, c), at)),
+            },
+            5 => match c {
+                'm' => Some(MASCULINE),
+                'f' => Some(FEMININE),
+                'n' => Some(NEUTER),
+                'p' | 'o' | 'q' | 'r' | 'x' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownGender(parsing, c), at)),
+            },
+            6 => match c {
+                'n' => Some(NOMINATIVE),
+                'a' => Some(ACCUSATIVE),
+                'g' => Some(GENITIVE),
+                'd' => Some(DATIVE),
+                'v' => Some(VOCATIVE),
+                'o' | 'c' | 'e' | 'b' | 'i' | 'l' | 'x' | 'z' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnknownCase(parsing, c), at)),
+            },
+            7 => match (pos, c) {
+                (_, 'p') => Some(UNKNOWN),
+                (NOUN, 'c') => Some(COMPARATIVE_NOUN),
+                (NOUN, 's') => Some(SUPERLATIVE_NOUN),
+                (ADJECTIVE, 'c') => Some(COMPARATIVE_ADJECTIVE),
+                (ADJECTIVE, 's') => Some(SUPERLATIVE_ADJECTIVE),
+                (ADVERB, 'c') => Some(COMPARATIVE_ADVERB),
+                (ADVERB, 's') => Some(SUPERLATIVE_ADVERB),
+                (_, 'c' | 's' | 'x' | 'z') => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnexpectedCharacter(parsing, c), at)),
+            },
+            8 => match c {
+                'w' | 's' | 't' => None,
+                _ => return Err(reader.fail(ParseErrorKind::UnexpectedCharacter(parsing, c), at)),
+            },
+            _ => match (pos, c) {
+                (NOUN | PROPER_NOUN | NUMERAL, 'n') => Some(INDECLINABLE),
+                (_, 'n' | 'i') => Some(UNKNOWN),
+                _ => return Err(reader.fail(ParseErrorKind::UnexpectedCharacter(parsing, c), at)),
+            },
+        };
+
+        // Every PROIEL part of speech has all ten positions, so nothing
+        // in the morphology stops a person or tense on, say, a noun. A
+        // parsing keeps a tense, mood and voice only on verbs, and a
+        // person only where the Robinson code has room for one: verbs,
+        // reflexive pronouns, the first and second person pronouns such
+        // as P-1NS, and the first or second person possessor of S-1SNSM.
+        // P-1NS and P-2NS have no gender. The rest are reported.
+        let value = match (index, value) {
+            (0, Some(THIRD_PERSON)) if matches!(pos, PERSONAL_PRONOUN | POSSESSIVE_PRONOUN) => None,
+            (0, _) if !has_person => None,
+            (2..=4, _) if !verb => None,
+            (5, _) if pos == PERSONAL_PRONOUN && person(parsing) != 0 => None,
+            (_, value) => value,
+        };
+        match value {
+            Some(value) if index == 7 => {
+                if value != UNKNOWN {
+                    parsing = set_part_of_speech(parsing, value);
+                }
+            }
+            Some(value) => parsing |= value,
+            None => reader.lose(index, at, c),
+        }
+    }
+
+    Ok(reader.finish(parsing))
+}
+
+// to_codes writes a parsing as a PROIEL part of speech and morphology.
+pub fn to_codes(parsed: u32) -> (String, String) {
+    let pos = part_of_speech(parsed);
+    let (tag, degree) = match pos {
+        VERB => ("V-", '-'),
+        NOUN => ("Nb", '-'),
+        COMPARATIVE_NOUN => ("Nb", 'c'),
+        SUPERLATIVE_NOUN => ("Nb", 's'),
+        PROPER_NOUN => ("Ne", '-'),
+        ADJECTIVE => ("A-", 'p'),
+        COMPARATIVE_ADJECTIVE => ("A-", 'c'),
+        SUPERLATIVE_ADJECTIVE => ("A-", 's'),
+        ADVERB if is_interrogative(parsed) => ("Du", '-'),
+        ADVERB | PARTICLE => ("Df", '-'),
+        COMPARATIVE_ADVERB => ("Df", 'c'),
+        SUPERLATIVE_ADVERB => ("Df", 's'),
+        CONJUNCTION | CONDITIONAL => ("C-", '-'),
+        PREPOSITION => ("R-", '-'),
+        ARTICLE => ("S-", '-'),
+        INTERJECTION => ("I-", '-'),
+        NUMERAL => ("Ma", '-'),
+        PERSONAL_PRONOUN => ("Pp", '-'),
+        REFLEXIVE_PRONOUN => ("Pk", '-'),
+        POSSESSIVE_PRONOUN => ("Ps", '-'),
+        RELATIVE_PRONOUN => ("Pr", '-'),
+        DEMONSTRATIVE_PRONOUN => ("Pd", '-'),
+        RECIPROCAL_PRONOUN => ("Pc", '-'),
+        PRONOUN if is_interrogative(parsed) => ("Pi", '-'),
+        PRONOUN => ("Px", '-'),
+        TRANSLITERATION | HEBREW_TRANSLITERATION | ARAMAIC_TRANSLITERATION | LETTER => ("F-", '-'),
+        _ => ("X-", '-'),
+    };
+    let verb = pos == VERB;
+    let inflecting = verb
+        || (case(parsed) != 0 && !is_indeclinable(parsed))
+        || matches!(
+            tag,
+            "A-" | "Pp" | "Pk" | "Ps" | "Pr" | "Pd" | "Pc" | "Pi" | "Px" | "S-"
+        );
+
+    let mut s = String::with_capacity(MORPHOLOGY_LENGTH);
+    s.push(match person(parsed) {
+        FIRST_PERSON if verb || tag == "Pp" || tag == "Pk" || tag == "Ps" => '1',
+        SECOND_PERSON if verb || tag == "Pp" || tag == "Pk" || tag == "Ps" => '2',
+        THIRD_PERSON if verb || tag == "Pp" || tag == "Pk" || tag == "Ps" => '3',
+        _ => '-',
+    });
+    s.push(match number(parsed) {
+        SINGULAR => 's',
+        DUAL => 'd',
+        PLURAL => 'p',
+        _ => '-',
+    });
+    if verb {
+        s.push(match tense(parsed) {
+            PRESENT => 'p',
+            IMPERFECT => 'i',
+            PERFECT | SECOND_PERFECT => 'r',
+            PLUPERFECT | SECOND_PLUPERFECT => 'l',
+            FUTURE_PERFECT | SECOND_FUTURE_PERFECT => 't',
+            FUTURE | SECOND_FUTURE => 'f',
+            AORIST | SECOND_AORIST => 'a',
+            _ => '-',
+        });
+        s.push(match mood(parsed) {
+            INDICATIVE_MOOD => 'i',
+            SUBJUNCTIVE_MOOD => 's',
+            IMPERATIVE_MOOD => 'm',
+            OPTATIVE_MOOD => 'o',
+            INFINITIVE_MOOD => 'n',
+            PARTICIPLE_MOOD => 'p',
+            _ => '-',
+        });
+        s.push(match voice(parsed) {
+            ACTIVE_VOICE => 'a',
+            PASSIVE_VOICE | PASSIVE_DEPONENT_VOICE => 'p',
+            MIDDLE_VOICE | MIDDLE_DEPONENT_VOICE => 'm',
+            MIDDLE_PASSIVE_VOICE | MIDDLE_PASSIVE_DEPONENT_VOICE => 'e',
+            _ => '-',
+        });
+    } else {
+        s.push_str("---");
+    }
+    s.push(match gender(parsed) {
+        MASCULINE => 'm',
+        FEMININE => 'f',
+        NEUTER => 'n',
+        _ => '-',
+    });
+    s.push(match case(parsed) {
+        NOMINATIVE => 'n',
+        ACCUSATIVE => 'a',
+        GENITIVE => 'g',
+        DATIVE => 'd',
+        VOCATIVE => 'v',
+        _ => '-',
+    });
+    s.push(degree);
+    s.push('-');
+    s.push(if inflecting { 'i' } else { 'n' });
+
+    (tag.to_string(), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_proiel_codes() {
+        for (pos, morphology, code) in [
+            ("V-", "3sria----i", "V-RAI-3S"),
+            ("V-", "-pppemn--i", "V-PEP-NPM"),
+            ("V-", "--ana----i", "V-AAN"),
+            ("Nb", "-s---mn--i", "N-NSM"),
+            ("Ne", "-s---mg--n", "IPN-GSM"),
+            ("A-", "-p---fdc-i", "A-DPF-C"),
+            ("A-", "-s---nap-i", "A-ASN"),
+            ("S-", "-s---ng--i", "T-GSN"),
+            ("Pp", "1s----n--i", "P-1NS"),
+            ("Pd", "-p---ma--i", "D-APM"),
+            ("Df", "---------n", "ADV"),
+            ("Du", "---------n", "ADV-I"),
+            ("C-", "---------n", "CONJ"),
+            ("R-", "---------n", "PREP"),
+        ] {
+            let (parsed, lost) = match from_codes(pos, morphology) {
+                Ok(result) => result,
+                Err(e) => panic!("{} {}: {}", pos, morphology, e),
+            };
+            assert_eq!(to_string(parsed), code, "{} {}", pos, morphology);
+            assert!(lost.is_empty(), "{}: {:?}", morphology, lost);
+            assert_eq!(to_codes(parsed), (pos.to_string(), morphology.to_string()));
+        }
+        assert_eq!(
+            to_codes(from_string("V-2AMP-NPM").unwrap()),
+            ("V-".to_string(), "-papmmn--i".to_string())
+        );
+    }
+
+    #[test]
+    fn test_proiel_report() {
+        let (parsed, lost) = from_codes("Pp", "1s---mn--i").unwrap();
+        assert_eq!(to_string(parsed), "P-1NS");
+        assert_eq!(
+            lost,
+            vec![Unrepresentable {
+                position: 8,
+                value: 'm',
+                name: "gender"
+            }]
+        );
+        let (parsed, lost) = from_codes("Pp", "3s---mn--i").unwrap();
+        assert_eq!(to_string(parsed), "P-NSM");
+        assert_eq!(lost[0].name, "person");
+        assert_eq!(lost[0].value, '3');
+        for (pos, morphology) in [
+            ("Pp", "1s---mn--i"),
+            ("Pp", "3s---mn--i"),
+            ("Pk", "3s---ma--i"),
+        ] {
+            let (parsed, _) = from_codes(pos, morphology).unwrap();
+            assert_eq!(crate::validate::validate(parsed), Ok(()), "{}", morphology);
+            assert_eq!(
+                from_string(&to_string(parsed)),
+                Ok(parsed),
+                "{}",
+                morphology
+            );
+        }
+        // A third person possessive has no Robinson code, so the person
+        // is reported and the parsing is left without one.
+        let (parsed, lost) = from_codes("Ps", "3s---mn--i").unwrap();
+        assert_eq!(
+            parsed,
+            POSSESSIVE_PRONOUN | SINGULAR | MASCULINE | NOMINATIVE
+        );
+        assert_eq!(lost[0].name, "person");
+        let (parsed, lost) = from_codes("Ps", "1s---mn--i").unwrap();
+        assert_eq!(to_string(parsed), "S-1SNSM");
+        assert!(lost.is_empty());
+
+        let (parsed, lost) = from_codes("Nb", "-s---qbxwi").unwrap();
+        assert_eq!(parsed, NOUN | SINGULAR);
+        let names: Vec<&str> = lost.iter().map(|u| u.name).collect();
+        assert_eq!(names, ["gender", "case", "degree", "strength"]);
+        assert_eq!(lost[0].position, 8);
+        assert_eq!(lost[0].value, 'q');
+
+        let (parsed, lost) = from_codes("V-", "3spga----i").unwrap();
+        assert_eq!(
+            parsed,
+            VERB | THIRD_PERSON | SINGULAR | PRESENT | ACTIVE_VOICE
+        );
+        assert_eq!(lost[0].name, "mood");
+
+        let e = from_codes("V-", "3sqia----i").unwrap_err();
+        assert_eq!(
+            e.kind,
+            ParseErrorKind::UnknownTenseForm(VERB | THIRD_PERSON | SINGULAR, 'q')
+        );
+        assert_eq!(e.position, 5);
+        assert_eq!(
+            from_codes("Zz", "----------").unwrap_err().kind,
+            ParseErrorKind::UnknownPartOfSpeech("Zz".to_string())
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::string::*;
+use std::fmt;
+use std::str::FromStr;
+
+// RawParsing keeps a parsing together with the way it was spelled in
+// the source data, so that a file can be read and written back out
+// byte for byte. Only the differences from the code written by
+// to_string() are stored: surrounding brackets, lower case, and any
+// other spelling such as CONJ-P for CONJ.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawParsing {
+    parsing: u32,
+    open: Option<char>,
+    close: Option<char>,
+    lowercase: bool,
+    spelling: Option<String>,
+}
+
+impl RawParsing {
+    pub fn new(parsing: u32) -> RawParsing {
+        RawParsing {
+            parsing,
+            open: None,
+            close: None,
+            lowercase: false,
+            spelling: None,
+        }
+    }
+
+    // from_source reads a code as leniently as from_string() does and
+    // remembers how it was written.
+    pub fn from_source(code: &str) -> Result<RawParsing, ParseError> {
+        let parsing = from_string(code)?;
+
+        let mut body = code;
+        let mut open = None;
+        let mut close = None;
+        if let Some(c) = body.chars().next().filter(|c| *c == '{' || *c == '[') {
+            open = Some(c);
+            body = &body[1..];
+        }
+        if let Some(c) = body.chars().last().filter(|c| *c == '}' || *c == ']') {
+            close = Some(c);
+            body = &body[..body.len() - 1];
+        }
+
+        let canonical = to_string(parsing);
+        let mut lowercase = false;
+        let mut spelling = None;
+        if body != canonical {
+            if body == canonical.to_lowercase() {
+                lowercase = true;
+            } else {
+                spelling = Some(body.to_string());
+            }
+        }
+
+        Ok(RawParsing {
+            parsing,
+            open,
+            close,
+            lowercase,
+            spelling,
+        })
+    }
+
+    pub fn parsing(&self) -> u32 {
+        self.parsing
+    }
+
+    // set_parsing replaces the parsing. Brackets and lower case are kept,
+    // but a non standard spelling no longer applies and is dropped.
+    pub fn set_parsing(&mut self, parsing: u32) {
+        if parsing != self.parsing {
+            self.parsing = parsing;
+            self.spelling = None;
+        }
+    }
+
+    pub fn brackets(&self) -> (Option<char>, Option<char>) {
+        (self.open, self.close)
+    }
+
+    pub fn is_lowercase(&self) -> bool {
+        self.lowercase
+    }
+
+    // spelling returns the code as written when it differs from the code
+    // written by to_string() by more than brackets and case.
+    pub fn spelling(&self) -> Option<&str> {
+        self.spelling.as_deref()
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.open.is_none() && self.close.is_none() && !self.lowercase && self.spelling.is_none()
+    }
+
+    // to_source_string writes the code exactly as it was read.
+    pub fn to_source_string(&self) -> String {
+        let mut s = String::new();
+        if let Some(c) = self.open {
+            s.push(c);
+        }
+        match &self.spelling {
+            Some(spelling) => s.push_str(spelling),
+            None if self.lowercase => s.push_str(&to_string(self.parsing).to_lowercase()),
+            None => s.push_str(&to_string(self.parsing)),
+        }
+        if let Some(c) = self.close {
+            s.push(c);
+        }
+        s
+    }
+}
+
+impl From<u32> for RawParsing {
+    fn from(parsing: u32) -> RawParsing {
+        RawParsing::new(parsing)
+    }
+}
+
+impl FromStr for RawParsing {
+    type Err = ParseError;
+
+    fn from_str(code: &str) -> Result<RawParsing, ParseError> {
+        RawParsing::from_source(code)
+    }
+}
+
+impl fmt::Display for RawParsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_source_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::*;
+
+    #[test]
+    fn test_raw_parsing() {
+        let raw = RawParsing::from_source("CONJ-P").expect("parse failed");
+        assert_eq!(raw.parsing(), CONJUNCTION);
+        assert_eq!(raw.spelling(), Some("CONJ-P"));
+        assert_eq!(raw.to_source_string(), "CONJ-P");
+
+        let mut raw = RawParsing::from_source("{n-nsm}").expect("parse failed");
+        assert_eq!(raw.parsing(), NOUN | NOMINATIVE | SINGULAR | MASCULINE);
+        assert_eq!(raw.brackets(), (Some('{'), Some('}')));
+        assert!(raw.is_lowercase());
+        assert_eq!(raw.to_source_string(), "{n-nsm}");
+
+        raw.set_parsing(set_case(raw.parsing(), GENITIVE));
+        assert_eq!(raw.to_source_string(), "{n-gsm}");
+
+        let mut raw = RawParsing::from_source("[V-APS2P]").expect("parse failed");
+        assert_eq!(raw.to_source_string(), "[V-APS2P]");
+        raw.set_parsing(set_person(raw.parsing(), FIRST_PERSON));
+        assert_eq!(raw.to_source_string(), "[V-APS-1P]");
+
+        assert!(RawParsing::from(ARTICLE | GENITIVE | SINGULAR | NEUTER).is_canonical());
+        assert!(RawParsing::from_source("Z-NSM").is_err());
+    }
+
+    #[test]
+    fn test_raw_parsing_files() {
+        for file in [
+            test_case!("nestle-parsing.txt"),
+            test_case!("byz-parsing.txt"),
+        ] {
+            let data = std::fs::read_to_string(file);
+            assert!(data.is_ok(), "data file missing. {}", file);
+            let data = data.unwrap();
+            for line in data.split('\n') {
+                if line == "form_morph" || line == "strongs" {
+                    continue;
+                }
+                match RawParsing::from_source(line) {
+                    Ok(raw) => assert_eq!(raw.to_source_string(), line),
+                    Err(e) => panic!("Parsing {} failed. {:?}", line, e),
+                }
+            }
+        }
+    }
+}
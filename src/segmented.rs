@@ -0,0 +1,183 @@
+use crate::hebrew::*;
+use std::fmt;
+use std::str::FromStr;
+
+// Role is the place a segment takes in a word. Hebrew words may have a
+// conjunction, preposition or article prefixed to them and a pronominal
+// or paragogic suffix after them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    Prefix,
+    Stem,
+    Suffix,
+}
+
+impl Role {
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Prefix => "prefix",
+            Role::Stem => "stem",
+            Role::Suffix => "suffix",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub role: Role,
+    pub parsing: u32,
+}
+
+// SegmentedParsing holds the parsing of each segment of a word in the
+// order they are written, such as HR/Ncfsc/Sp3ms. Each segment is
+// stored in the hebrew encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct SegmentedParsing {
+    segments: Vec<Segment>,
+}
+
+impl SegmentedParsing {
+    // new gives each segment a role. Suffixes are the segments with the
+    // suffix part of speech, the last segment before them is the stem
+    // and any segments before that are prefixes.
+    pub fn new(parts: &[u32]) -> SegmentedParsing {
+        let stem = parts
+            .iter()
+            .rposition(|p| part_of_speech(*p) != SUFFIX)
+            .unwrap_or(0);
+        let segments = parts
+            .iter()
+            .enumerate()
+            .map(|(i, &parsing)| Segment {
+                role: if part_of_speech(parsing) == SUFFIX {
+                    Role::Suffix
+                } else if i < stem {
+                    Role::Prefix
+                } else {
+                    Role::Stem
+                },
+                parsing,
+            })
+            .collect();
+        SegmentedParsing { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Segment> {
+        self.segments.iter()
+    }
+
+    // with_role returns the parsings of the segments with a role, in
+    // the order they are written.
+    pub fn with_role(&self, role: Role) -> impl Iterator<Item = u32> + '_ {
+        self.segments
+            .iter()
+            .filter(move |s| s.role == role)
+            .map(|s| s.parsing)
+    }
+
+    pub fn stem(&self) -> Option<u32> {
+        self.with_role(Role::Stem).next()
+    }
+
+    pub fn prefixes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.with_role(Role::Prefix)
+    }
+
+    pub fn suffixes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.with_role(Role::Suffix)
+    }
+
+    pub fn parsings(&self) -> Vec<u32> {
+        self.segments.iter().map(|s| s.parsing).collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a SegmentedParsing {
+    type Item = &'a Segment;
+    type IntoIter = std::slice::Iter<'a, Segment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments.iter()
+    }
+}
+
+impl FromStr for SegmentedParsing {
+    type Err = HebrewError;
+
+    fn from_str(code: &str) -> Result<SegmentedParsing, HebrewError> {
+        parse_word(code).map(|parts| SegmentedParsing::new(&parts))
+    }
+}
+
+impl fmt::Display for SegmentedParsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&word_to_string(&self.parsings()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segmented_parsing() {
+        let word: SegmentedParsing = "HR/Ncfsc/Sp3ms".parse().unwrap();
+        let roles: Vec<Role> = word.iter().map(|s| s.role).collect();
+        assert_eq!(roles, vec![Role::Prefix, Role::Stem, Role::Suffix]);
+        assert_eq!(
+            word.stem(),
+            Some(NOUN | COMMON_NOUN | FEMININE | SINGULAR | CONSTRUCT)
+        );
+        assert_eq!(word.prefixes().collect::<Vec<u32>>(), vec![PREPOSITION]);
+        assert_eq!(
+            word.suffixes().collect::<Vec<u32>>(),
+            vec![SUFFIX | PRONOMINAL_SUFFIX | THIRD_PERSON | MASCULINE | SINGULAR]
+        );
+        assert_eq!(word.to_string(), "HR/Ncfsc/Sp3ms");
+
+        let word: SegmentedParsing = "HC/Vqw3ms".parse().unwrap();
+        assert_eq!(word.len(), 2);
+        assert_eq!(word.prefixes().collect::<Vec<u32>>(), vec![CONJUNCTION]);
+        assert_eq!(word.suffixes().count(), 0);
+
+        let word: SegmentedParsing = "HC/R/Td/Ncmpa".parse().unwrap();
+        assert_eq!(word.prefixes().count(), 3);
+        assert_eq!(
+            word.stem(),
+            Some(NOUN | COMMON_NOUN | MASCULINE | PLURAL | ABSOLUTE)
+        );
+
+        assert_eq!(
+            "HR/Ncfsc/Sp3xs".parse::<SegmentedParsing>(),
+            Err(HebrewError::Unknown("gender", 'x', 12))
+        );
+    }
+    #[test]
+    fn test_segmented_oshb_file() {
+        let file = crate::test_case!("oshb-parsing.txt");
+        let data = std::fs::read_to_string(file).expect("data file missing");
+        for line in data.lines() {
+            let word: SegmentedParsing = line.parse().unwrap();
+            assert_eq!(word.parsings(), parse_word(line).unwrap(), "{}", line);
+            assert_eq!(word.to_string(), line);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::enumerate::representable;
+use crate::string::*;
+use std::sync::OnceLock;
+
+// The most suggestions returned by suggest().
+pub const MAX_SUGGESTIONS: usize = 10;
+
+// Swapping two neighbouring characters is the most common slip, so it
+// costs half as much as inserting, deleting or replacing a character.
+// Suggestions further than two ordinary edits away are not returned.
+const TRANSPOSE_COST: usize = 1;
+const EDIT_COST: usize = 2;
+const MAX_COST: usize = 2 * EDIT_COST;
+
+fn known_codes() -> &'static [(Vec<char>, u32)] {
+    static CODES: OnceLock<Vec<(Vec<char>, u32)>> = OnceLock::new();
+    CODES.get_or_init(|| {
+        representable()
+            .into_iter()
+            .map(|p| (to_string(p).chars().collect(), p))
+            .collect()
+    })
+}
+
+// suggest returns the valid parsings whose codes are closest to code,
+// best first. Codes are compared by edit distance, where swapping two
+// neighbouring characters counts as a smaller mistake than any other
+// edit, so "V-AIA-3S" suggests "V-AAI-3S" before anything else.
+pub fn suggest(code: &str) -> Vec<u32> {
+    let code = code.trim();
+    let code = code.strip_prefix(['{', '[']).unwrap_or(code);
+    let code = code.strip_suffix(['}', ']']).unwrap_or(code);
+    let code: Vec<char> = code.chars().map(|c| c.to_ascii_uppercase()).collect();
+
+    let mut found: Vec<(usize, &[char], u32)> = known_codes()
+        .iter()
+        .filter_map(|(known, p)| {
+            let cost = distance(&code, known);
+            if cost <= MAX_COST {
+                Some((cost, known.as_slice(), *p))
+            } else {
+                None
+            }
+        })
+        .collect();
+    found.sort();
+    found
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, p)| p)
+        .collect()
+}
+
+// distance is the optimal string alignment distance between a and b.
+fn distance(a: &[char], b: &[char]) -> usize {
+    if a.len().abs_diff(b.len()) * EDIT_COST > MAX_COST {
+        return usize::MAX;
+    }
+
+    let width = b.len() + 1;
+    let mut d = vec![0; (a.len() + 1) * width];
+    for i in 0..=a.len() {
+        d[i * width] = i * EDIT_COST;
+    }
+    for (j, cell) in d.iter_mut().take(width).enumerate() {
+        *cell = j * EDIT_COST;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let replace = if a[i - 1] == b[j - 1] { 0 } else { EDIT_COST };
+            let mut cost = (d[(i - 1) * width + j] + EDIT_COST)
+                .min(d[i * width + j - 1] + EDIT_COST)
+                .min(d[(i - 1) * width + j - 1] + replace);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(d[(i - 2) * width + j - 2] + TRANSPOSE_COST);
+            }
+            d[i * width + j] = cost;
+        }
+    }
+    d[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::*;
+
+    #[test]
+    fn test_suggest() {
+        let found = suggest("V-AIA-3S");
+        assert_eq!(to_string(found[0]), "V-AAI-3S");
+        assert!(found.len() <= MAX_SUGGESTIONS);
+
+        let found = suggest("N-NMS");
+        assert_eq!(to_string(found[0]), "N-NSM");
+
+        let found = suggest("v-2aap-apx");
+        assert!(found.contains(&from_string("V-2AAP-APF").unwrap()));
+        assert!(found.iter().all(|p| part_of_speech(*p) == VERB));
+
+        // A valid code is its own best suggestion.
+        assert_eq!(suggest("T-GSN")[0], ARTICLE | GENITIVE | SINGULAR | NEUTER);
+
+        assert!(suggest("this is not a parsing code").is_empty());
+    }
+}
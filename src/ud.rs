@@ -0,0 +1,684 @@
+use crate::parse::*;
+use std::fmt;
+use std::str::FromStr;
+
+// Universal Dependencies tags each word with a universal part of speech
+// (UPOS) and a list of features (FEATS) such as "Case=Gen|Number=Sing".
+// The features follow the UD Ancient Greek treebanks:
+//
+//   Present      Aspect=Imp|Tense=Pres    Aorist      Aspect=Perf|Tense=Past
+//   Imperfect    Aspect=Imp|Tense=Past    Perfect     Aspect=Perf|Tense=Pres
+//   Future       Tense=Fut                Pluperfect  Aspect=Perf|Tense=Pqp
+//   Future perf. Aspect=Perf|Tense=Fut
+//
+// UD has no second tenses and no deponents, so to_ud() loses them:
+// second tenses are written as the first tense, and the deponent voices
+// as Mid, Pass and Mid,Pass. from_ud() reads these back as the first
+// tense and the plain voice. Crasis, attic forms and the correlative
+// and indeclinable flags are also dropped, as are the interrogative and
+// indefinite flags on any part of speech but a pronoun, adverb or
+// particle, such as the interrogative proper noun PN-GSM-I. Hebrew and
+// Aramaic transliterations are both written as X with Foreign=Yes.
+//
+// from_ud() keeps a person only where a Robinson code has room for one.
+// A personal pronoun with Person=3 is read as the P-NSM form, which has
+// no person, and the gender of a first or second person pronoun is
+// dropped, as P-1NS has none.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Upos {
+    Adj,
+    Adp,
+    Adv,
+    Aux,
+    Cconj,
+    Det,
+    Intj,
+    Noun,
+    Num,
+    Part,
+    Pron,
+    Propn,
+    Punct,
+    Sconj,
+    Sym,
+    Verb,
+    X,
+}
+
+impl Upos {
+    pub const ALL: &'static [Upos] = &[
+        Upos::Adj,
+        Upos::Adp,
+        Upos::Adv,
+        Upos::Aux,
+        Upos::Cconj,
+        Upos::Det,
+        Upos::Intj,
+        Upos::Noun,
+        Upos::Num,
+        Upos::Part,
+        Upos::Pron,
+        Upos::Propn,
+        Upos::Punct,
+        Upos::Sconj,
+        Upos::Sym,
+        Upos::Verb,
+        Upos::X,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Upos::Adj => "ADJ",
+            Upos::Adp => "ADP",
+            Upos::Adv => "ADV",
+            Upos::Aux => "AUX",
+            Upos::Cconj => "CCONJ",
+            Upos::Det => "DET",
+            Upos::Intj => "INTJ",
+            Upos::Noun => "NOUN",
+            Upos::Num => "NUM",
+            Upos::Part => "PART",
+            Upos::Pron => "PRON",
+            Upos::Propn => "PROPN",
+            Upos::Punct => "PUNCT",
+            Upos::Sconj => "SCONJ",
+            Upos::Sym => "SYM",
+            Upos::Verb => "VERB",
+            Upos::X => "X",
+        }
+    }
+}
+
+impl fmt::Display for Upos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Upos {
+    type Err = UdError;
+
+    fn from_str(name: &str) -> Result<Upos, UdError> {
+        Upos::ALL
+            .iter()
+            .copied()
+            .find(|upos| upos.name() == name)
+            .ok_or_else(|| UdError::UnknownUpos(name.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UdError {
+    UnknownUpos(String),
+    // A part of speech that has no parsing, such as PUNCT.
+    Unsupported(Upos),
+    // A feature that is not written as Name=Value, or is given twice.
+    Malformed(String),
+    UnknownFeature(String),
+    UnknownValue(String, String),
+    // A feature that does not apply to the part of speech.
+    Unexpected(String),
+}
+
+impl fmt::Display for UdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UdError::UnknownUpos(upos) => write!(f, "unknown UPOS '{}'", upos),
+            UdError::Unsupported(upos) => write!(f, "{} has no parsing", upos),
+            UdError::Malformed(feature) => write!(f, "malformed feature '{}'", feature),
+            UdError::UnknownFeature(name) => write!(f, "unknown feature '{}'", name),
+            UdError::UnknownValue(name, value) => {
+                write!(f, "unknown value '{}' for feature {}", value, name)
+            }
+            UdError::Unexpected(name) => {
+                write!(f, "feature {} does not apply to this part of speech", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UdError {}
+
+const FEATURES: &[&str] = &[
+    "Abbr",
+    "Aspect",
+    "Case",
+    "Definite",
+    "Degree",
+    "Foreign",
+    "Gender",
+    "Mood",
+    "Number",
+    "Number[psor]",
+    "Person",
+    "Polarity",
+    "Poss",
+    "PronType",
+    "Reflex",
+    "Tense",
+    "VerbForm",
+    "Voice",
+];
+
+// to_ud writes a parsing as a UPOS and FEATS. Features are sorted by
+// name and an empty list is written as "_".
+pub fn to_ud(parsed: u32) -> (Upos, String) {
+    let mut feats: Vec<(&str, &str)> = Vec::new();
+    let pos = part_of_speech(parsed);
+    let upos = match pos {
+        VERB => Upos::Verb,
+        NOUN | LETTER => Upos::Noun,
+        COMPARATIVE_NOUN => {
+            feats.push(("Degree", "Cmp"));
+            Upos::Noun
+        }
+        SUPERLATIVE_NOUN => {
+            feats.push(("Degree", "Sup"));
+            Upos::Noun
+        }
+        PROPER_NOUN => Upos::Propn,
+        ADJECTIVE => Upos::Adj,
+        COMPARATIVE_ADJECTIVE => {
+            feats.push(("Degree", "Cmp"));
+            Upos::Adj
+        }
+        SUPERLATIVE_ADJECTIVE => {
+            feats.push(("Degree", "Sup"));
+            Upos::Adj
+        }
+        NUMERAL => Upos::Num,
+        ADVERB => Upos::Adv,
+        COMPARATIVE_ADVERB => {
+            feats.push(("Degree", "Cmp"));
+            Upos::Adv
+        }
+        SUPERLATIVE_ADVERB => {
+            feats.push(("Degree", "Sup"));
+            Upos::Adv
+        }
+        CONJUNCTION => Upos::Cconj,
+        CONDITIONAL => Upos::Sconj,
+        PREPOSITION => Upos::Adp,
+        PARTICLE => Upos::Part,
+        INTERJECTION => Upos::Intj,
+        ARTICLE => {
+            feats.push(("Definite", "Def"));
+            feats.push(("PronType", "Art"));
+            Upos::Det
+        }
+        POSSESSIVE_PRONOUN => {
+            feats.push(("Poss", "Yes"));
+            feats.push(("PronType", "Prs"));
+            feats.push((
+                "Number[psor]",
+                if ref_number(parsed) == REF_PLURAL {
+                    "Plur"
+                } else {
+                    "Sing"
+                },
+            ));
+            Upos::Det
+        }
+        PERSONAL_PRONOUN => {
+            feats.push(("PronType", "Prs"));
+            Upos::Pron
+        }
+        REFLEXIVE_PRONOUN => {
+            feats.push(("PronType", "Prs"));
+            feats.push(("Reflex", "Yes"));
+            Upos::Pron
+        }
+        RELATIVE_PRONOUN => {
+            feats.push(("PronType", "Rel"));
+            Upos::Pron
+        }
+        DEMONSTRATIVE_PRONOUN => {
+            feats.push(("PronType", "Dem"));
+            Upos::Pron
+        }
+        RECIPROCAL_PRONOUN => {
+            feats.push(("PronType", "Rcp"));
+            Upos::Pron
+        }
+        PRONOUN => Upos::Pron,
+        TRANSLITERATION | HEBREW_TRANSLITERATION | ARAMAIC_TRANSLITERATION => {
+            feats.push(("Foreign", "Yes"));
+            Upos::X
+        }
+        _ => Upos::X,
+    };
+
+    // The pronoun flags are written as a PronType on the parts of speech
+    // that do not already have one.
+    if matches!(pos, PRONOUN | ADVERB | PARTICLE) {
+        if is_interrogative(parsed) {
+            feats.push(("PronType", "Int"));
+        } else if is_indefinite(parsed) {
+            feats.push(("PronType", "Ind"));
+        }
+    }
+    if is_negative(parsed) {
+        feats.push(("Polarity", "Neg"));
+    }
+    if is_abbreviated(parsed) {
+        feats.push(("Abbr", "Yes"));
+    }
+
+    if pos == VERB {
+        let (aspect, tense) = match tense(parsed) {
+            PRESENT => (Some("Imp"), Some("Pres")),
+            IMPERFECT => (Some("Imp"), Some("Past")),
+            FUTURE | SECOND_FUTURE => (None, Some("Fut")),
+            AORIST | SECOND_AORIST => (Some("Perf"), Some("Past")),
+            PERFECT | SECOND_PERFECT => (Some("Perf"), Some("Pres")),
+            PLUPERFECT | SECOND_PLUPERFECT => (Some("Perf"), Some("Pqp")),
+            FUTURE_PERFECT | SECOND_FUTURE_PERFECT => (Some("Perf"), Some("Fut")),
+            _ => (None, None),
+        };
+        if let Some(aspect) = aspect {
+            feats.push(("Aspect", aspect));
+        }
+        if let Some(tense) = tense {
+            feats.push(("Tense", tense));
+        }
+        let voice = match voice(parsed) {
+            ACTIVE_VOICE => Some("Act"),
+            MIDDLE_VOICE | MIDDLE_DEPONENT_VOICE => Some("Mid"),
+            PASSIVE_VOICE | PASSIVE_DEPONENT_VOICE => Some("Pass"),
+            MIDDLE_PASSIVE_VOICE | MIDDLE_PASSIVE_DEPONENT_VOICE => Some("Mid,Pass"),
+            _ => None,
+        };
+        if let Some(voice) = voice {
+            feats.push(("Voice", voice));
+        }
+        let (mood, form) = match mood(parsed) {
+            INDICATIVE_MOOD => (Some("Ind"), Some("Fin")),
+            SUBJUNCTIVE_MOOD => (Some("Sub"), Some("Fin")),
+            OPTATIVE_MOOD => (Some("Opt"), Some("Fin")),
+            IMPERATIVE_MOOD => (Some("Imp"), Some("Fin")),
+            INFINITIVE_MOOD => (None, Some("Inf")),
+            PARTICIPLE_MOOD => (None, Some("Part")),
+            _ => (None, None),
+        };
+        if let Some(mood) = mood {
+            feats.push(("Mood", mood));
+        }
+        if let Some(form) = form {
+            feats.push(("VerbForm", form));
+        }
+    }
+
+    let person = match person(parsed) {
+        FIRST_PERSON => Some("1"),
+        SECOND_PERSON => Some("2"),
+        THIRD_PERSON => Some("3"),
+        _ => None,
+    };
+    if let Some(person) = person {
+        feats.push(("Person", person));
+    }
+    let case = match case(parsed) {
+        NOMINATIVE => Some("Nom"),
+        GENITIVE => Some("Gen"),
+        DATIVE => Some("Dat"),
+        ACCUSATIVE => Some("Acc"),
+        VOCATIVE => Some("Voc"),
+        _ => None,
+    };
+    if let Some(case) = case {
+        feats.push(("Case", case));
+    }
+    let number = match number(parsed) {
+        SINGULAR => Some("Sing"),
+        DUAL => Some("Dual"),
+        PLURAL => Some("Plur"),
+        _ => None,
+    };
+    if let Some(number) = number {
+        feats.push(("Number", number));
+    }
+    let gender = match gender(parsed) {
+        MASCULINE => Some("Masc"),
+        FEMININE => Some("Fem"),
+        NEUTER => Some("Neut"),
+        _ => None,
+    };
+    if let Some(gender) = gender {
+        feats.push(("Gender", gender));
+    }
+
+    if feats.is_empty() {
+        return (upos, "_".to_string());
+    }
+    feats.sort_by_key(|(name, _)| name.to_lowercase());
+    let feats: Vec<String> = feats
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    (upos, feats.join("|"))
+}
+
+// from_ud reads a UPOS and FEATS into a parsing. AUX is read as a verb.
+pub fn from_ud(upos: Upos, feats: &str) -> Result<u32, UdError> {
+    let mut features: Vec<(&str, &str)> = Vec::new();
+    if feats != "_" && !feats.is_empty() {
+        for feature in feats.split('|') {
+            let (name, value) = match feature.split_once('=') {
+                Some(pair) => pair,
+                None => return Err(UdError::Malformed(feature.to_string())),
+            };
+            if !FEATURES.contains(&name) {
+                return Err(UdError::UnknownFeature(name.to_string()));
+            }
+            if features.iter().any(|(n, _)| *n == name) {
+                return Err(UdError::Malformed(feature.to_string()));
+            }
+            features.push((name, value));
+        }
+    }
+    let get = |name: &str| {
+        features
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+    };
+    let unknown =
+        |name: &str, value: &str| UdError::UnknownValue(name.to_string(), value.to_string());
+
+    let pron_type = get("PronType");
+    let mut p = match (upos, pron_type) {
+        (Upos::Verb | Upos::Aux, _) => VERB,
+        (Upos::Noun, _) => NOUN,
+        (Upos::Propn, _) => PROPER_NOUN,
+        (Upos::Adj, _) => ADJECTIVE,
+        (Upos::Num, _) => NUMERAL,
+        (Upos::Adv, _) => ADVERB,
+        (Upos::Cconj, _) => CONJUNCTION,
+        (Upos::Sconj, _) => CONDITIONAL,
+        (Upos::Adp, _) => PREPOSITION,
+        (Upos::Part, _) => PARTICLE,
+        (Upos::Intj, _) => INTERJECTION,
+        (Upos::X, _) if get("Foreign") == Some("Yes") => TRANSLITERATION,
+        (Upos::X, _) => UNKNOWN,
+        (Upos::Det | Upos::Pron, _) if get("Poss") == Some("Yes") => POSSESSIVE_PRONOUN,
+        (Upos::Det | Upos::Pron, _) if get("Reflex") == Some("Yes") => REFLEXIVE_PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Art")) => ARTICLE,
+        (Upos::Det | Upos::Pron, Some("Prs")) => PERSONAL_PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Rel")) => RELATIVE_PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Dem")) => DEMONSTRATIVE_PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Rcp")) => RECIPROCAL_PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Int")) => INTERROGATIVE | PRONOUN,
+        (Upos::Det | Upos::Pron, Some("Ind")) => INDEFINITE | PRONOUN,
+        (Upos::Det | Upos::Pron, None) => PRONOUN,
+        (Upos::Det | Upos::Pron, Some(value)) => return Err(unknown("PronType", value)),
+        (Upos::Punct | Upos::Sym, _) => return Err(UdError::Unsupported(upos)),
+    };
+    let pos = part_of_speech(p);
+
+    match (pos, pron_type) {
+        (ADVERB | PARTICLE, Some("Int")) => p |= INTERROGATIVE,
+        (ADVERB | PARTICLE, Some("Ind")) => p |= INDEFINITE,
+        (PRONOUN | PERSONAL_PRONOUN | REFLEXIVE_PRONOUN | POSSESSIVE_PRONOUN, _)
+        | (ARTICLE | RELATIVE_PRONOUN | DEMONSTRATIVE_PRONOUN | RECIPROCAL_PRONOUN, _)
+        | (_, None) => {}
+        (_, Some(_)) => return Err(UdError::Unexpected("PronType".to_string())),
+    }
+
+    if let Some(degree) = get("Degree") {
+        let compared = match (pos, degree) {
+            (_, "Pos") => pos,
+            (NOUN, "Cmp") => COMPARATIVE_NOUN,
+            (NOUN, "Sup") => SUPERLATIVE_NOUN,
+            (ADJECTIVE, "Cmp") => COMPARATIVE_ADJECTIVE,
+            (ADJECTIVE, "Sup") => SUPERLATIVE_ADJECTIVE,
+            (ADVERB, "Cmp") => COMPARATIVE_ADVERB,
+            (ADVERB, "Sup") => SUPERLATIVE_ADVERB,
+            (_, "Cmp" | "Sup") => return Err(UdError::Unexpected("Degree".to_string())),
+            _ => return Err(unknown("Degree", degree)),
+        };
+        p = set_part_of_speech(p, compared);
+    }
+
+    match get("Polarity") {
+        Some("Neg") => p |= NEGATIVE,
+        Some(value) => return Err(unknown("Polarity", value)),
+        None => {}
+    }
+    match get("Abbr") {
+        Some("Yes") if pos != VERB => p |= ABBREVIATED,
+        Some("Yes") => return Err(UdError::Unexpected("Abbr".to_string())),
+        Some(value) => return Err(unknown("Abbr", value)),
+        None => {}
+    }
+    match get("Number[psor]") {
+        Some("Plur") if pos == POSSESSIVE_PRONOUN => p |= REF_PLURAL,
+        Some("Sing") if pos == POSSESSIVE_PRONOUN => {}
+        Some("Sing" | "Plur") => return Err(UdError::Unexpected("Number[psor]".to_string())),
+        Some(value) => return Err(unknown("Number[psor]", value)),
+        None => {}
+    }
+
+    if pos == VERB {
+        p |= match (get("Tense"), get("Aspect")) {
+            (None, None) => 0,
+            (Some("Pres"), None | Some("Imp")) => PRESENT,
+            (Some("Pres"), Some("Perf")) => PERFECT,
+            (Some("Past"), Some("Imp")) => IMPERFECT,
+            (Some("Past"), None | Some("Perf")) => AORIST,
+            (Some("Pqp"), None | Some("Perf")) => PLUPERFECT,
+            (Some("Fut"), None | Some("Imp")) => FUTURE,
+            (Some("Fut"), Some("Perf")) => FUTURE_PERFECT,
+            (Some("Pres" | "Past" | "Pqp" | "Fut") | None, Some(aspect)) => {
+                return Err(unknown("Aspect", aspect))
+            }
+            (Some(tense), _) => return Err(unknown("Tense", tense)),
+        };
+        p |= match get("Voice") {
+            None => 0,
+            Some("Act") => ACTIVE_VOICE,
+            Some("Mid") => MIDDLE_VOICE,
+            Some("Pass") => PASSIVE_VOICE,
+            Some("Mid,Pass") => MIDDLE_PASSIVE_VOICE,
+            Some(value) => return Err(unknown("Voice", value)),
+        };
+        p |= match (get("VerbForm"), get("Mood")) {
+            (None, None) => 0,
+            (Some("Fin") | None, Some("Ind")) => INDICATIVE_MOOD,
+            (Some("Fin") | None, Some("Sub")) => SUBJUNCTIVE_MOOD,
+            (Some("Fin") | None, Some("Opt")) => OPTATIVE_MOOD,
+            (Some("Fin") | None, Some("Imp")) => IMPERATIVE_MOOD,
+            (Some("Inf"), None) => INFINITIVE_MOOD,
+            (Some("Part"), None) => PARTICIPLE_MOOD,
+            (Some("Fin"), None) => 0,
+            (Some("Fin") | None, Some(value)) => return Err(unknown("Mood", value)),
+            (Some("Inf" | "Part"), Some(_)) => return Err(UdError::Unexpected("Mood".to_string())),
+            (Some(value), _) => return Err(unknown("VerbForm", value)),
+        };
+    } else {
+        for name in ["Tense", "Aspect", "Voice", "Mood", "VerbForm"] {
+            if get(name).is_some() {
+                return Err(UdError::Unexpected(name.to_string()));
+            }
+        }
+    }
+
+    match get("Definite") {
+        Some("Def") if pos == ARTICLE => {}
+        Some("Def") => return Err(UdError::Unexpected("Definite".to_string())),
+        Some(value) => return Err(unknown("Definite", value)),
+        None => {}
+    }
+
+    let person = match get("Person") {
+        None => 0,
+        Some("1") => FIRST_PERSON,
+        Some("2") => SECOND_PERSON,
+        Some("3") => THIRD_PERSON,
+        Some(value) => return Err(unknown("Person", value)),
+    };
+    p |= match (pos, person) {
+        (_, 0) => 0,
+        (VERB | REFLEXIVE_PRONOUN, _) => person,
+        (PERSONAL_PRONOUN, THIRD_PERSON) => 0,
+        (PERSONAL_PRONOUN | POSSESSIVE_PRONOUN, FIRST_PERSON | SECOND_PERSON) => person,
+        _ => return Err(UdError::Unexpected("Person".to_string())),
+    };
+    p |= match get("Case") {
+        None => 0,
+        Some("Nom") => NOMINATIVE,
+        Some("Gen") => GENITIVE,
+        Some("Dat") => DATIVE,
+        Some("Acc") => ACCUSATIVE,
+        Some("Voc") => VOCATIVE,
+        Some(value) => return Err(unknown("Case", value)),
+    };
+    p |= match get("Number") {
+        None => 0,
+        Some("Sing") => SINGULAR,
+        Some("Dual") => DUAL,
+        Some("Plur") => PLURAL,
+        Some(value) => return Err(unknown("Number", value)),
+    };
+    let gender = match get("Gender") {
+        None => 0,
+        Some("Masc") => MASCULINE,
+        Some("Fem") => FEMININE,
+        Some("Neut") => NEUTER,
+        Some(value) => return Err(unknown("Gender", value)),
+    };
+    if pos != PERSONAL_PRONOUN || p & PERSON_MASK == 0 {
+        p |= gender;
+    }
+
+    Ok(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::*;
+
+    #[test]
+    fn test_ud() {
+        for (code, upos, feats) in [
+            (
+                "V-AAI-1P",
+                Upos::Verb,
+                "Aspect=Perf|Mood=Ind|Number=Plur|Person=1|Tense=Past|VerbForm=Fin|Voice=Act",
+            ),
+            (
+                "V-PEP-GSF",
+                Upos::Verb,
+                "Aspect=Imp|Case=Gen|Gender=Fem|Number=Sing|Tense=Pres|VerbForm=Part|Voice=Mid,Pass",
+            ),
+            ("V-RAN", Upos::Verb, "Aspect=Perf|Tense=Pres|VerbForm=Inf|Voice=Act"),
+            ("N-NSM", Upos::Noun, "Case=Nom|Gender=Masc|Number=Sing"),
+            ("A-GPN-C", Upos::Adj, "Case=Gen|Degree=Cmp|Gender=Neut|Number=Plur"),
+            ("T-DSF", Upos::Det, "Case=Dat|Definite=Def|Gender=Fem|Number=Sing|PronType=Art"),
+            (
+                "S-2PAPM",
+                Upos::Det,
+                "Case=Acc|Gender=Masc|Number=Plur|Number[psor]=Plur|Person=2|Poss=Yes|PronType=Prs",
+            ),
+            ("I-NSM", Upos::Pron, "Case=Nom|Gender=Masc|Number=Sing|PronType=Int"),
+            ("ADV-N", Upos::Adv, "Polarity=Neg"),
+            ("CONJ", Upos::Cconj, "_"),
+        ] {
+            let parsed = from_string(code).unwrap();
+            assert_eq!(to_ud(parsed), (upos, feats.to_string()), "{}", code);
+            assert_eq!(from_ud(upos, feats), Ok(parsed), "{}", code);
+        }
+
+        // Second tenses and deponents read back as the plain forms.
+        let (upos, feats) = to_ud(from_string("V-2ADI-3S").unwrap());
+        assert_eq!(to_string(from_ud(upos, &feats).unwrap()), "V-AMI-3S");
+        assert_eq!("PROPN".parse(), Ok(Upos::Propn));
+        assert_eq!(
+            from_ud(Upos::Aux, "Mood=Ind|VerbForm=Fin"),
+            Ok(VERB | INDICATIVE_MOOD)
+        );
+    }
+
+    #[test]
+    fn test_ud_errors() {
+        assert_eq!(
+            "VRB".parse::<Upos>(),
+            Err(UdError::UnknownUpos("VRB".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Case=Loc"),
+            Err(UdError::UnknownValue("Case".to_string(), "Loc".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Tense=Past"),
+            Err(UdError::Unexpected("Tense".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Typo=Yes"),
+            Err(UdError::UnknownFeature("Typo".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Case"),
+            Err(UdError::Malformed("Case".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Case=Nom|Case=Gen"),
+            Err(UdError::Malformed("Case=Gen".to_string()))
+        );
+        assert_eq!(
+            from_ud(
+                Upos::Det,
+                "Case=Nom|Definite=Ind|Gender=Masc|Number=Sing|PronType=Art"
+            ),
+            Err(UdError::UnknownValue(
+                "Definite".to_string(),
+                "Ind".to_string()
+            ))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Case=Nom|Definite=Def|Gender=Masc|Number=Sing"),
+            Err(UdError::Unexpected("Definite".to_string()))
+        );
+        assert_eq!(
+            from_ud(Upos::Noun, "Case=Nom|Gender=Masc|Number=Sing|Person=3"),
+            Err(UdError::Unexpected("Person".to_string()))
+        );
+        assert_eq!(
+            from_ud(
+                Upos::Det,
+                "Case=Nom|Gender=Masc|Number=Sing|Person=3|Poss=Yes|PronType=Prs"
+            ),
+            Err(UdError::Unexpected("Person".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_ud_person() {
+        // Only the person a Robinson code can write is kept.
+        for (feats, code) in [
+            (
+                "Case=Nom|Gender=Masc|Number=Sing|Person=3|PronType=Prs",
+                "P-NSM",
+            ),
+            (
+                "Case=Nom|Gender=Masc|Number=Sing|Person=1|PronType=Prs",
+                "P-1NS",
+            ),
+            ("Case=Dat|Number=Plur|Person=2|PronType=Prs", "P-2DP"),
+            (
+                "Case=Acc|Gender=Masc|Number=Sing|Person=3|PronType=Prs|Reflex=Yes",
+                "F-3ASM",
+            ),
+        ] {
+            let parsed = from_ud(Upos::Pron, feats).unwrap();
+            assert_eq!(to_string(parsed), code, "{}", feats);
+            assert_eq!(from_string(code), Ok(parsed), "{}", feats);
+            assert_eq!(crate::validate::validate(parsed), Ok(()), "{}", feats);
+        }
+        assert_eq!(
+            from_ud(Upos::Punct, "_"),
+            Err(UdError::Unsupported(Upos::Punct))
+        );
+    }
+}
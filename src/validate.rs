@@ -0,0 +1,440 @@
+use crate::fields::*;
+use crate::parse::*;
+use crate::string::{from_string, to_string};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidityIssue {
+    // A field the parsing needs is empty.
+    Missing(Field),
+    // A field is set that does not apply to the part of speech or mood.
+    Unexpected(Field),
+    // A field holds a value that is not one of the constants in parse.rs.
+    Invalid(Field),
+    // A field holds a value that can not go with the rest of the
+    // parsing, such as a first person imperative.
+    Impossible(Field),
+}
+
+impl ValidityIssue {
+    pub fn field(self) -> Field {
+        match self {
+            ValidityIssue::Missing(field)
+            | ValidityIssue::Unexpected(field)
+            | ValidityIssue::Invalid(field)
+            | ValidityIssue::Impossible(field) => field,
+        }
+    }
+}
+
+impl fmt::Display for ValidityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidityIssue::Missing(field) => write!(f, "{} is missing", field),
+            ValidityIssue::Unexpected(field) => write!(f, "{} does not apply", field),
+            ValidityIssue::Invalid(field) => write!(f, "{} is not a valid value", field),
+            ValidityIssue::Impossible(field) => {
+                write!(f, "{} is not possible with the other fields", field)
+            }
+        }
+    }
+}
+
+// Parts of speech that decline, and so need a case and number. Those
+// that also need a gender are marked true.
+fn declension(p: u32) -> Option<bool> {
+    match part_of_speech(p) {
+        NOUN | ADJECTIVE | PROPER_NOUN | PRONOUN => Some(false),
+        ARTICLE
+        | RELATIVE_PRONOUN
+        | RECIPROCAL_PRONOUN
+        | DEMONSTRATIVE_PRONOUN
+        | REFLEXIVE_PRONOUN
+        | POSSESSIVE_PRONOUN
+        | SUPERLATIVE_NOUN
+        | SUPERLATIVE_ADJECTIVE
+        | COMPARATIVE_NOUN
+        | COMPARATIVE_ADJECTIVE => Some(true),
+        // First and second person pronouns have no gender.
+        PERSONAL_PRONOUN => Some(person(p) == 0),
+        _ => None,
+    }
+}
+
+// validate checks that a parsing makes grammatical sense, for example
+// that a participle has a case, number and gender, that an infinitive
+// has no person, and that only verbs have a tense. A parsing must also
+// be one that a Robinson code can hold, so that it can be written out.
+// Every problem found is returned, each naming the field at fault.
+pub fn validate(p: u32) -> Result<(), Vec<ValidityIssue>> {
+    let mut issues = Vec::new();
+    let pos = part_of_speech(p);
+
+    let check = |issues: &mut Vec<ValidityIssue>, field: Field, valid: bool| {
+        if p & field.mask() != 0 && !valid {
+            issues.push(ValidityIssue::Invalid(field));
+        }
+    };
+    check(
+        &mut issues,
+        Field::PartOfSpeech,
+        PartOfSpeech::from_parsing(p).is_some(),
+    );
+    check(&mut issues, Field::Voice, Voice::from_parsing(p).is_some());
+    check(&mut issues, Field::Mood, Mood::from_parsing(p).is_some());
+    check(&mut issues, Field::Case, Case::from_parsing(p).is_some());
+    check(
+        &mut issues,
+        Field::Number,
+        Number::from_parsing(p).is_some(),
+    );
+    check(
+        &mut issues,
+        Field::Gender,
+        Gender::from_parsing(p).is_some(),
+    );
+    let unexpected = |issues: &mut Vec<ValidityIssue>, field: Field| {
+        if p & field.mask() != 0 {
+            issues.push(ValidityIssue::Unexpected(field));
+        }
+    };
+    let missing = |issues: &mut Vec<ValidityIssue>, field: Field| {
+        if p & field.mask() == 0 {
+            issues.push(ValidityIssue::Missing(field));
+        }
+    };
+
+    // Only these parts of speech have an interrogative form.
+    if is_interrogative(p) && !matches!(pos, PRONOUN | PROPER_NOUN | ADVERB | PARTICLE) {
+        issues.push(ValidityIssue::Impossible(Field::PartOfSpeech));
+    }
+
+    if pos == VERB {
+        check(&mut issues, Field::Tense, Tense::from_parsing(p).is_some());
+        missing(&mut issues, Field::Tense);
+        missing(&mut issues, Field::Voice);
+        missing(&mut issues, Field::Mood);
+        match mood(p) {
+            PARTICIPLE_MOOD => {
+                unexpected(&mut issues, Field::Person);
+                missing(&mut issues, Field::Case);
+                missing(&mut issues, Field::Number);
+                missing(&mut issues, Field::Gender);
+            }
+            INFINITIVE_MOOD => {
+                unexpected(&mut issues, Field::Person);
+                unexpected(&mut issues, Field::Case);
+                unexpected(&mut issues, Field::Number);
+                unexpected(&mut issues, Field::Gender);
+            }
+            0 => {}
+            m => {
+                missing(&mut issues, Field::Person);
+                missing(&mut issues, Field::Number);
+                unexpected(&mut issues, Field::Case);
+                unexpected(&mut issues, Field::Gender);
+                if m == IMPERATIVE_MOOD && person(p) == FIRST_PERSON {
+                    issues.push(ValidityIssue::Impossible(Field::Person));
+                }
+            }
+        }
+        return finish(p, issues);
+    }
+
+    unexpected(&mut issues, Field::Voice);
+    unexpected(&mut issues, Field::Mood);
+
+    // Non verbs may only use the tense bits for the possessive pronoun
+    // referent number and the abbreviated flag.
+    let overloaded = if pos == POSSESSIVE_PRONOUN {
+        REF_PLURAL | ABBREVIATED
+    } else {
+        ABBREVIATED
+    };
+    if p & TENSE_MASK & !overloaded != 0 {
+        issues.push(ValidityIssue::Unexpected(Field::Tense));
+    }
+
+    match pos {
+        // A third person pronoun is written P-NSM with no person, and
+        // the first and second person pronouns P-1NS and P-2NS have no
+        // gender.
+        PERSONAL_PRONOUN => match person(p) {
+            0 => {}
+            THIRD_PERSON => issues.push(ValidityIssue::Impossible(Field::Person)),
+            _ if gender(p) != 0 => issues.push(ValidityIssue::Impossible(Field::Gender)),
+            _ => {}
+        },
+        REFLEXIVE_PRONOUN => missing(&mut issues, Field::Person),
+        POSSESSIVE_PRONOUN => {
+            missing(&mut issues, Field::Person);
+            if person(p) == THIRD_PERSON {
+                issues.push(ValidityIssue::Impossible(Field::Person));
+            }
+        }
+        _ => unexpected(&mut issues, Field::Person),
+    }
+
+    match declension(p) {
+        Some(_) if is_indeclinable(p) && case(p) == 0 => {
+            unexpected(&mut issues, Field::Number);
+            unexpected(&mut issues, Field::Gender);
+        }
+        Some(needs_gender) => {
+            missing(&mut issues, Field::Case);
+            missing(&mut issues, Field::Number);
+            if needs_gender {
+                missing(&mut issues, Field::Gender);
+            }
+        }
+        None if is_indeclinable(p) => {}
+        None => {
+            unexpected(&mut issues, Field::Case);
+            unexpected(&mut issues, Field::Number);
+            unexpected(&mut issues, Field::Gender);
+        }
+    }
+
+    finish(p, issues)
+}
+
+fn finish(p: u32, mut issues: Vec<ValidityIssue>) -> Result<(), Vec<ValidityIssue>> {
+    if issues.is_empty() {
+        if let Some(field) = unwritten(p) {
+            issues.push(ValidityIssue::Impossible(field));
+        }
+    }
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+// unwritten returns the first field that is lost when the parsing is
+// written as a code and read back, or the part of speech when only its
+// flags are lost.
+fn unwritten(p: u32) -> Option<Field> {
+    let back = from_string(&to_string(p)).unwrap_or(UNKNOWN);
+    if back == p {
+        return None;
+    }
+    let field = [
+        Field::PartOfSpeech,
+        Field::Tense,
+        Field::Voice,
+        Field::Mood,
+        Field::Person,
+        Field::Case,
+        Field::Number,
+        Field::Gender,
+    ]
+    .into_iter()
+    .find(|field| p & field.mask() != back & field.mask());
+    Some(field.unwrap_or(Field::PartOfSpeech))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        for code in [
+            "V-2AAP-APF",
+            "V-PAN",
+            "V-AAI-1P",
+            "N-NSM",
+            "N-NS",
+            "S-2PAPM",
+            "P-1AS",
+            "P-NSM",
+            "F-3APM",
+            "N-PRI",
+            "A-NUI-ABB",
+            "ADV",
+            "CONJ-K",
+            "T-GSN",
+        ] {
+            assert_eq!(validate(from_string(code).unwrap()), Ok(()), "{}", code);
+        }
+
+        assert_eq!(
+            validate(from_string("V-PAM-1S").unwrap()),
+            Err(vec![ValidityIssue::Impossible(Field::Person)])
+        );
+        assert_eq!(
+            validate(VERB | NOMINATIVE),
+            Err(vec![
+                ValidityIssue::Missing(Field::Tense),
+                ValidityIssue::Missing(Field::Voice),
+                ValidityIssue::Missing(Field::Mood),
+            ])
+        );
+        assert_eq!(
+            validate(VERB | PRESENT | ACTIVE_VOICE | INFINITIVE_MOOD | THIRD_PERSON),
+            Err(vec![ValidityIssue::Unexpected(Field::Person)])
+        );
+        assert_eq!(
+            validate(VERB | PRESENT | ACTIVE_VOICE | PARTICIPLE_MOOD | NOMINATIVE | SINGULAR),
+            Err(vec![ValidityIssue::Missing(Field::Gender)])
+        );
+        assert_eq!(
+            validate(VERB | PRESENT | ACTIVE_VOICE | INDICATIVE_MOOD | SINGULAR),
+            Err(vec![ValidityIssue::Missing(Field::Person)])
+        );
+        assert_eq!(
+            validate(NOUN | AORIST | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Unexpected(Field::Tense)])
+        );
+        assert_eq!(
+            validate(ARTICLE | REF_PLURAL | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Unexpected(Field::Tense)])
+        );
+        assert_eq!(
+            validate(INTERROGATIVE | NOUN | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Impossible(Field::PartOfSpeech)])
+        );
+        assert_eq!(validate(from_string("PN-GSM-I").unwrap()), Ok(()));
+        assert_eq!(
+            validate(PERSONAL_PRONOUN | FIRST_PERSON | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Impossible(Field::Gender)])
+        );
+        assert_eq!(
+            validate(PERSONAL_PRONOUN | THIRD_PERSON | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Impossible(Field::Person)])
+        );
+        assert_eq!(
+            validate(REFLEXIVE_PRONOUN | GENITIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Missing(Field::Person)])
+        );
+        assert_eq!(
+            validate(CORRELATIVE | NOUN | NOMINATIVE | SINGULAR | MASCULINE),
+            Err(vec![ValidityIssue::Impossible(Field::PartOfSpeech)])
+        );
+        assert_eq!(
+            validate(LETTER),
+            Err(vec![ValidityIssue::Impossible(Field::PartOfSpeech)])
+        );
+        assert_eq!(
+            validate(ADVERB | GENITIVE),
+            Err(vec![ValidityIssue::Unexpected(Field::Case)])
+        );
+        assert_eq!(
+            validate(NOUN | (7 << 19) | SINGULAR),
+            Err(vec![ValidityIssue::Invalid(Field::Case)])
+        );
+        assert_eq!(
+            validate(31).unwrap_err()[0],
+            ValidityIssue::Invalid(Field::PartOfSpeech)
+        );
+        assert_eq!(ValidityIssue::Missing(Field::Gender).field(), Field::Gender);
+        assert_eq!(
+            ValidityIssue::Missing(Field::Gender).to_string(),
+            "gender is missing"
+        );
+    }
+
+    // Every parsing that validate() accepts can be written as a code and
+    // read back. All values of every field are tried without flags, and
+    // every set of flags on a few parsings of each part of speech.
+    #[test]
+    fn test_validate_representable() {
+        let check = |p: u32| {
+            if validate(p).is_ok() {
+                let code = to_string(p);
+                assert_eq!(from_string(&code), Ok(p), "{:#x} {}", p, code);
+            }
+        };
+        let values = |mask: u32| -> Vec<u32> {
+            let shift = mask.trailing_zeros();
+            (0..=mask >> shift).map(|v| v << shift).collect()
+        };
+
+        for pos in 0..=PART_OF_SPEECH_MASK {
+            let verb = pos == VERB;
+            let tenses = if verb {
+                values(TENSE_MASK)
+            } else {
+                vec![0, REF_PLURAL]
+            };
+            let (voices, moods) = if verb {
+                (values(VOICE_MASK), values(MOOD_MASK))
+            } else {
+                (vec![0], vec![0])
+            };
+            for &tense in &tenses {
+                for &voice in &voices {
+                    for &mood in &moods {
+                        for person in values(PERSON_MASK) {
+                            for case in values(CASE_MASK) {
+                                for number in values(NUMBER_MASK) {
+                                    for gender in [0, MASCULINE, FEMININE, NEUTER] {
+                                        check(
+                                            pos | tense
+                                                | voice
+                                                | mood
+                                                | person
+                                                | case
+                                                | number
+                                                | gender,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let flags = [
+            INTERROGATIVE,
+            NEGATIVE,
+            CORRELATIVE,
+            INDEFINITE,
+            INDECLINABLE,
+            CRASIS,
+            ATTIC,
+            ABBREVIATED,
+        ];
+        let shapes = [
+            0,
+            NOMINATIVE | SINGULAR,
+            NOMINATIVE | SINGULAR | MASCULINE,
+            FIRST_PERSON | NOMINATIVE | SINGULAR,
+            FIRST_PERSON | NOMINATIVE | SINGULAR | MASCULINE,
+            REF_PLURAL | SECOND_PERSON | NOMINATIVE | SINGULAR | MASCULINE,
+            PRESENT | ACTIVE_VOICE | INDICATIVE_MOOD | FIRST_PERSON | SINGULAR,
+            PRESENT | ACTIVE_VOICE | PARTICIPLE_MOOD | NOMINATIVE | SINGULAR | MASCULINE,
+            PRESENT | ACTIVE_VOICE | INFINITIVE_MOOD,
+        ];
+        for pos in 0..=PART_OF_SPEECH_MASK {
+            for shape in shapes {
+                for subset in 0..1 << flags.len() {
+                    let p = flags
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| subset & (1 << i) != 0)
+                        .fold(pos | shape, |p, (_, flag)| p | flag);
+                    check(p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_corpus() {
+        for file in [
+            test_case!("nestle-parsing.txt"),
+            test_case!("byz-parsing.txt"),
+        ] {
+            let data = std::fs::read_to_string(file).expect("data file missing");
+            for line in data.split("\n") {
+                if let Ok(parsed) = from_string(line) {
+                    assert_eq!(validate(parsed), Ok(()), "{}", line);
+                }
+            }
+        }
+    }
+}